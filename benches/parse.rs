@@ -0,0 +1,113 @@
+//! Benchmarks for parsing a capture with the Channel, MCS, and VHT fields.
+//!
+//! The header benchmark parses a capture with a single present word, run it
+//! with `--features smallvec` to compare against storing the present fields
+//! inline. The allocations made parsing that capture are counted and printed
+//! first, for the same comparison.
+//!
+//! To compare against another revision save a baseline there with
+//! `cargo bench -- --save-baseline old` and then run `cargo bench -- --baseline
+//! old` on this one.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use radiotap::{
+    field::{self, Header},
+    Radiotap, RadiotapIterator,
+};
+
+/// The system allocator, counting the allocations made.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const CAPTURE: [u8; 28] = [
+    0, 0, 28, 0, 8, 0, 40, 0, 60, 20, 64, 1, 63, 52, 7, 0, 68, 0, 0, 4, 33, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// Returns the number of allocations made by each call of the function.
+fn count_allocations<T>(f: impl Fn() -> T) -> usize {
+    const RUNS: usize = 1000;
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..RUNS {
+        black_box(f());
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) / RUNS
+}
+
+fn allocations(_: &mut Criterion) {
+    let feature = if cfg!(feature = "smallvec") {
+        "with smallvec"
+    } else {
+        "without smallvec"
+    };
+    let header = count_allocations(|| {
+        let header: Header = field::from_bytes(black_box(&CAPTURE)).unwrap();
+        header
+    });
+    let radiotap = count_allocations(|| Radiotap::from_bytes(black_box(&CAPTURE)).unwrap());
+    println!("Header::from_bytes allocations {}: {}", feature, header);
+    println!("Radiotap::from_bytes allocations {}: {}", feature, radiotap);
+}
+
+fn header(c: &mut Criterion) {
+    c.bench_function("Header::from_bytes", |b| {
+        b.iter(|| {
+            let header: Header = field::from_bytes(black_box(&CAPTURE)).unwrap();
+            header
+        })
+    });
+}
+
+fn parse(c: &mut Criterion) {
+    c.bench_function("Radiotap::from_bytes", |b| {
+        b.iter(|| Radiotap::from_bytes(black_box(&CAPTURE)).unwrap())
+    });
+}
+
+fn iterate(c: &mut Criterion) {
+    c.bench_function("RadiotapIterator", |b| {
+        b.iter(|| {
+            for element in RadiotapIterator::from_bytes(black_box(&CAPTURE)).unwrap() {
+                black_box(element.unwrap());
+            }
+        })
+    });
+}
+
+fn fields(c: &mut Criterion) {
+    c.bench_function("field::from_bytes", |b| {
+        b.iter(|| {
+            let channel: field::Channel = field::from_bytes(black_box(&CAPTURE[8..12])).unwrap();
+            let mcs: field::MCS = field::from_bytes(black_box(&CAPTURE[12..15])).unwrap();
+            let vht: field::VHT = field::from_bytes(black_box(&CAPTURE[16..28])).unwrap();
+            (channel, mcs, vht)
+        })
+    });
+}
+
+criterion_group!(benches, allocations, header, parse, iterate, fields);
+criterion_main!(benches);
@@ -15,6 +15,10 @@ impl field::Field for MyAntennaSignal {
             value: input[0] as i8,
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.value as u8]
+    }
 }
 
 fn main() {
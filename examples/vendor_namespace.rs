@@ -0,0 +1,76 @@
+//! This example demonstrates how to parse the fields of a vendor namespace by
+//! implementing the `ns::Namespace` trait. It checks the decoded fields, and
+//! runs as a test with `cargo test --examples`.
+
+use radiotap::{
+    field::{Kind, Oui},
+    ns::Namespace,
+    Error, RadiotapIterator,
+};
+
+/// Our vendor namespace, with a 16-bit temperature in bit 0 and an 8-bit
+/// channel load in bit 1.
+#[derive(Debug, Default)]
+struct MyNamespace {
+    temperature: Option<u16>,
+    load: Option<u8>,
+}
+
+impl Namespace for MyNamespace {
+    fn oui(&self) -> Oui {
+        [0x00, 0x11, 0x22]
+    }
+
+    fn layout(&self, bit: u8) -> Option<(u64, usize)> {
+        match bit {
+            0 => Some((2, 2)),
+            1 => Some((1, 1)),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, bit: u8, data: &[u8]) -> Result<(), Error> {
+        match bit {
+            0 => self.temperature = Some(u16::from_le_bytes([data[0], data[1]])),
+            1 => self.load = Some(data[0]),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let capture = [
+        0, 0, 28, 0, 6, 0, 0, 192, 3, 0, 0, 160, 32, 0, 0, 0, 16, 12, 0, 17, 34, 0, 3, 0, 42, 0,
+        200, 186,
+    ];
+
+    let mut namespace = MyNamespace::default();
+    let kinds: Vec<Kind> = RadiotapIterator::from_bytes(&capture)
+        .unwrap()
+        .into_iter()
+        .vendor(&mut namespace)
+        .map(|element| element.unwrap().0)
+        .filter(|kind| !matches!(kind, Kind::VendorNamespace(_)))
+        .collect();
+
+    // The vendor fields are yielded in between the radiotap fields
+    assert_eq!(
+        kinds,
+        [
+            Kind::Flags,
+            Kind::Rate,
+            Kind::VendorField(0),
+            Kind::VendorField(1),
+            Kind::AntennaSignal,
+        ]
+    );
+    assert_eq!(namespace.temperature, Some(42));
+    assert_eq!(namespace.load, Some(200));
+    println!("{:?}", namespace);
+}
+
+#[test]
+fn vendor_namespace() {
+    main();
+}
@@ -0,0 +1,19 @@
+//! Parses arbitrary bytes as a Radiotap capture, which must never panic.
+//!
+//! Run with `cargo fuzz run parse` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use radiotap::{Radiotap, RadiotapIterator};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Radiotap::from_bytes(data);
+    let _ = Radiotap::from_bytes_lenient(data);
+
+    if let Ok(iterator) = RadiotapIterator::from_bytes(data) {
+        for field in iterator.into_iter() {
+            let _ = field;
+        }
+    }
+});
@@ -0,0 +1,31 @@
+//! Parses a Radiotap capture from a `no_std` crate, to check that the crate
+//! builds without `std` when its default features are disabled.
+//!
+//! Build for a target without `std` with
+//! `cargo build --target thumbv7em-none-eabihf` from this directory.
+
+#![no_std]
+
+use radiotap::Radiotap;
+
+/// Returns the antenna signal in dBm of the given capture, if it has one.
+pub fn antenna_signal(capture: &[u8]) -> Option<i8> {
+    let radiotap = Radiotap::from_bytes(capture).ok()?;
+    radiotap.antenna_signal.map(|signal| signal.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let capture = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        assert_eq!(antenna_signal(&capture), Some(-43));
+        assert_eq!(antenna_signal(&capture[..20]), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Checking the frame check sequence (FCS) of a captured 802.11 frame.
+//!
+//! When the [Flags](../field/struct.Flags.html) field has `fcs` set, the last
+//! 4 bytes of the 802.11 frame following the Radiotap capture are the CRC-32 of
+//! the rest of the frame.
+//!
+//! ```
+//! use radiotap::{fcs, Radiotap};
+//!
+//! let capture = [
+//!     0, 0, 9, 0, 2, 0, 0, 0, 0x10, 0xd4, 0, 0, 0, 0, 0x11, 0x22, 0x33, 0x44, 0x55, 113, 234,
+//!     242, 75,
+//! ];
+//!
+//! let (radiotap, payload) = Radiotap::parse(&capture).unwrap();
+//! assert_eq!(fcs::check(payload, &radiotap.flags.unwrap()), Some(true));
+//! ```
+
+use crate::field::Flags;
+
+/// Returns the 802.11 frame without the FCS, along with the FCS.
+///
+/// Returns `None` if the flags do not include an FCS or the payload is too
+/// short to contain one.
+pub fn split<'a>(payload: &'a [u8], flags: &Flags) -> Option<(&'a [u8], u32)> {
+    if !flags.fcs || payload.len() < 4 {
+        return None;
+    }
+
+    let (frame, fcs) = payload.split_at(payload.len() - 4);
+    Some((frame, u32::from_le_bytes([fcs[0], fcs[1], fcs[2], fcs[3]])))
+}
+
+/// The FCS stripped from a payload by [strip](fn.strip.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FcsResult<'a> {
+    /// The 4 bytes of the FCS.
+    pub bytes: [u8; 4],
+    /// Whether the FCS matches the CRC-32 of the frame, always `false` if the
+    /// flags have `bad_fcs` set.
+    pub valid: bool,
+    /// The 802.11 frame without the FCS.
+    pub stripped_payload: &'a [u8],
+}
+
+/// Returns the FCS stripped from the end of the payload and whether it is
+/// valid.
+///
+/// The FCS is stripped whenever the flags have `fcs` set, even if they also
+/// have `bad_fcs` set, in which case the CRC-32 is not computed and the FCS is
+/// invalid. Returns `None` if the flags do not include an FCS or the payload
+/// is too short to contain one.
+pub fn strip<'a>(payload: &'a [u8], flags: &Flags) -> Option<FcsResult<'a>> {
+    let (frame, fcs) = split(payload, flags)?;
+    Some(FcsResult {
+        bytes: fcs.to_le_bytes(),
+        valid: !flags.bad_fcs && crc32(frame) == fcs,
+        stripped_payload: frame,
+    })
+}
+
+/// Returns whether the FCS at the end of the payload matches the CRC-32 of the
+/// 802.11 frame, or `None` if the flags do not include an FCS.
+pub fn check(payload: &[u8], flags: &Flags) -> Option<bool> {
+    if !flags.fcs {
+        return None;
+    }
+
+    Some(match split(payload, flags) {
+        Some((frame, fcs)) => crc32(frame) == fcs,
+        None => false,
+    })
+}
+
+/// Returns the IEEE 802.3 CRC-32 of the data, as used for the 802.11 FCS.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Field;
+
+    /// An ACK frame followed by its FCS.
+    const ACK: [u8; 14] = [
+        0xd4, 0, 0, 0, 0, 0x11, 0x22, 0x33, 0x44, 0x55, 113, 234, 242, 75,
+    ];
+
+    #[test]
+    fn crc32() {
+        assert_eq!(super::crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(super::crc32(&[]), 0);
+    }
+
+    #[test]
+    fn check() {
+        let flags = Flags::from_bytes(&[0x10]).unwrap();
+        assert_eq!(super::check(&ACK, &flags), Some(true));
+        assert_eq!(split(&ACK, &flags), Some((&ACK[..10], 0x4bf2_ea71)));
+
+        let mut corrupted = ACK;
+        corrupted[4] ^= 0x01;
+        assert_eq!(super::check(&corrupted, &flags), Some(false));
+
+        assert_eq!(super::check(&ACK[..3], &flags), Some(false));
+
+        let flags = Flags::from_bytes(&[0x00]).unwrap();
+        assert_eq!(super::check(&ACK, &flags), None);
+        assert_eq!(split(&ACK, &flags), None);
+    }
+
+    #[test]
+    fn strip() {
+        let flags = Flags::from_bytes(&[0x10]).unwrap();
+        let result = super::strip(&ACK, &flags).unwrap();
+        assert_eq!(result.bytes, [113, 234, 242, 75]);
+        assert!(result.valid);
+        assert_eq!(result.stripped_payload, &ACK[..10]);
+
+        // The FCS is still stripped from a frame flagged with a bad FCS
+        let flags = Flags::from_bytes(&[0x50]).unwrap();
+        let result = super::strip(&ACK, &flags).unwrap();
+        assert_eq!(result.bytes, [113, 234, 242, 75]);
+        assert!(!result.valid);
+        assert_eq!(result.stripped_payload, &ACK[..10]);
+
+        // Without an FCS nothing is stripped, even if flagged with a bad FCS
+        let flags = Flags::from_bytes(&[0x40]).unwrap();
+        assert_eq!(super::strip(&ACK, &flags), None);
+
+        let flags = Flags::from_bytes(&[0x10]).unwrap();
+        assert_eq!(super::strip(&ACK[..3], &flags), None);
+    }
+}
@@ -1,8 +1,10 @@
 //! Extended Radiotap field definitions and parsers.
 
+use core::fmt;
+
 use crate::{Error, Result};
 
-const HT_RATE: [[f32; 4]; 32] = [
+const HT_RATE: [[f32; 4]; 77] = [
     // 20 MHz LGI,20 MHz SGI,40 MHZ LGI,40 MHz SGI
     [6.50, 7.20, 13.50, 15.00],
     [13.00, 14.40, 27.00, 30.00],
@@ -28,14 +30,59 @@ const HT_RATE: [[f32; 4]; 32] = [
     [156.00, 173.30, 324.00, 360.00],
     [175.50, 195.00, 364.50, 405.00],
     [195.00, 216.70, 405.00, 450.00],
-    [26.00, 28.80, 54.00, 60.00],
-    [52.00, 57.60, 108.00, 120.00],
-    [78.00, 86.80, 162.00, 180.00],
+    [26.00, 28.90, 54.00, 60.00],
+    [52.00, 57.80, 108.00, 120.00],
+    [78.00, 86.70, 162.00, 180.00],
     [104.00, 115.60, 216.00, 240.00],
-    [156.00, 173.20, 324.00, 360.00],
-    [208.00, 231.20, 432.00, 480.00],
+    [156.00, 173.30, 324.00, 360.00],
+    [208.00, 231.10, 432.00, 480.00],
     [234.00, 260.00, 486.00, 540.00],
-    [260.00, 288.80, 540.00, 600.00],
+    [260.00, 288.90, 540.00, 600.00],
+    [-1.00, -1.00, 6.00, 6.70],
+    [39.00, 43.30, 81.00, 90.00],
+    [52.00, 57.80, 108.00, 120.00],
+    [65.00, 72.20, 135.00, 150.00],
+    [58.50, 65.00, 121.50, 135.00],
+    [78.00, 86.70, 162.00, 180.00],
+    [97.50, 108.30, 202.50, 225.00],
+    [52.00, 57.80, 108.00, 120.00],
+    [65.00, 72.20, 135.00, 150.00],
+    [65.00, 72.20, 135.00, 150.00],
+    [78.00, 86.70, 162.00, 180.00],
+    [91.00, 101.10, 189.00, 210.00],
+    [91.00, 101.10, 189.00, 210.00],
+    [104.00, 115.60, 216.00, 240.00],
+    [78.00, 86.70, 162.00, 180.00],
+    [97.50, 108.30, 202.50, 225.00],
+    [97.50, 108.30, 202.50, 225.00],
+    [117.00, 130.00, 243.00, 270.00],
+    [136.50, 151.70, 283.50, 315.00],
+    [136.50, 151.70, 283.50, 315.00],
+    [156.00, 173.30, 324.00, 360.00],
+    [65.00, 72.20, 135.00, 150.00],
+    [78.00, 86.70, 162.00, 180.00],
+    [91.00, 101.10, 189.00, 210.00],
+    [78.00, 86.70, 162.00, 180.00],
+    [91.00, 101.10, 189.00, 210.00],
+    [104.00, 115.60, 216.00, 240.00],
+    [117.00, 130.00, 243.00, 270.00],
+    [104.00, 115.60, 216.00, 240.00],
+    [117.00, 130.00, 243.00, 270.00],
+    [130.00, 144.40, 270.00, 300.00],
+    [130.00, 144.40, 270.00, 300.00],
+    [143.00, 158.90, 297.00, 330.00],
+    [97.50, 108.30, 202.50, 225.00],
+    [117.00, 130.00, 243.00, 270.00],
+    [136.50, 151.70, 283.50, 315.00],
+    [117.00, 130.00, 243.00, 270.00],
+    [136.50, 151.70, 283.50, 315.00],
+    [156.00, 173.30, 324.00, 360.00],
+    [175.50, 195.00, 364.50, 405.00],
+    [156.00, 173.30, 324.00, 360.00],
+    [175.50, 195.00, 364.50, 405.00],
+    [195.00, 216.70, 405.00, 450.00],
+    [195.00, 216.70, 405.00, 450.00],
+    [214.50, 238.30, 445.50, 495.00],
 ];
 
 const VHT_RATE: [[f32; 8]; 80] = [
@@ -56,7 +103,7 @@ const VHT_RATE: [[f32; 8]; 80] = [
     [52.0, 57.8, 108.0, 120.0, 234.0, 260.0, 468.0, 520.0],
     [78.0, 86.7, 162.0, 180.0, 351.0, 390.0, 702.0, 780.0],
     [104.0, 115.6, 216.0, 240.0, 468.0, 520.0, 936.0, 1040.0],
-    [117.0, 130.3, 243.0, 270.0, 526.5, 585.0, 1053.0, 1170.0],
+    [117.0, 130.0, 243.0, 270.0, 526.5, 585.0, 1053.0, 1170.0],
     [130.0, 144.4, 270.0, 300.0, 585.0, 650.0, 1170.0, 1300.0],
     [156.0, 173.3, 324.0, 360.0, 702.0, 780.0, 1404.0, 1560.0],
     [-1.0, -1.0, 360.0, 400.0, 780.0, 866.7, 1560.0, 1733.3],
@@ -80,79 +127,91 @@ const VHT_RATE: [[f32; 8]; 80] = [
     [260.0, 288.9, 540.0, 600.0, 1170.0, 1300.0, 2340.0, 2600.0],
     [312.0, 346.7, 648.0, 720.0, 1404.0, 1560.0, 2808.0, 3120.0],
     [-1.0, -1.0, 720.0, 800.0, 1560.0, 1733.3, 3120.0, 3466.7],
-    [-1.0, -1.0, -1.0, -1.0, 146.3, 162.5, 292.5, 325.0],
-    [-1.0, -1.0, -1.0, -1.0, 292.5, 325.0, 585.0, 650.0],
-    [-1.0, -1.0, -1.0, -1.0, 438.8, 487.5, 877.5, 975.0],
-    [-1.0, -1.0, -1.0, -1.0, 585.0, 650.0, 1170.0, 1300.0],
-    [-1.0, -1.0, -1.0, -1.0, 877.5, 975.0, 1755.0, 1950.0],
-    [-1.0, -1.0, -1.0, -1.0, 1170.0, 1300.0, 2340.0, 2600.0],
-    [-1.0, -1.0, -1.0, -1.0, 1316.3, 1462.5, 2632.5, 2925.0],
-    [-1.0, -1.0, -1.0, -1.0, 1462.5, 1625.0, 2925.0, 3250.0],
-    [-1.0, -1.0, -1.0, -1.0, 1755.0, 1950.0, 3510.0, 3900.0],
-    [-1.0, -1.0, -1.0, -1.0, 1950.0, 2166.7, 3900.0, 4333.3],
-    [-1.0, -1.0, -1.0, -1.0, 175.5, 195.0, 351.0, 390.0],
-    [-1.0, -1.0, -1.0, -1.0, 351.0, 390.0, 702.0, 780.0],
-    [-1.0, -1.0, -1.0, -1.0, 526.5, 585.0, 1053.0, 1170.0],
-    [-1.0, -1.0, -1.0, -1.0, 702.0, 780.0, 1404.0, 1560.0],
-    [-1.0, -1.0, -1.0, -1.0, 1053.0, 1170.0, 2106.0, 2340.0],
-    [-1.0, -1.0, -1.0, -1.0, 1404.0, 1560.0, 2808.0, 3120.0],
-    [-1.0, -1.0, -1.0, -1.0, 1579.5, 1755.0, 3159.0, 3510.0],
-    [-1.0, -1.0, -1.0, -1.0, 1755.0, 1950.0, 3510.0, 3900.0],
-    [-1.0, -1.0, -1.0, -1.0, 2106.0, 2340.0, 4212.0, 4680.0],
-    [-1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 4680.0, 5200.0],
-    [-1.0, -1.0, -1.0, -1.0, 204.8, 227.5, 409.5, 455.0],
-    [-1.0, -1.0, -1.0, -1.0, 409.5, 455.0, 819.0, 910.0],
-    [-1.0, -1.0, -1.0, -1.0, 614.3, 682.5, 1228.5, 1365.0],
-    [-1.0, -1.0, -1.0, -1.0, 819.0, 910.0, 1638.0, 1820.0],
-    [-1.0, -1.0, -1.0, -1.0, 1228.5, 1365.0, 2457.0, 2730.0],
-    [-1.0, -1.0, -1.0, -1.0, 1638.0, 1820.0, 3276.0, 3640.0],
-    [-1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 3685.5, 4095.0],
-    [-1.0, -1.0, -1.0, -1.0, 2047.5, 2275.0, 4095.0, 4550.0],
-    [-1.0, -1.0, -1.0, -1.0, 2457.0, 2730.0, 4914.0, 5460.0],
-    [-1.0, -1.0, -1.0, -1.0, 2730.0, 3033.3, 5460.0, 6066.7],
-    [-1.0, -1.0, -1.0, -1.0, 234.0, 260.0, 468.0, 520.0],
-    [-1.0, -1.0, -1.0, -1.0, 468.0, 520.0, 936.0, 1040.0],
-    [-1.0, -1.0, -1.0, -1.0, 702.0, 780.0, 1404.0, 1560.0],
-    [-1.0, -1.0, -1.0, -1.0, 936.0, 1040.0, 1872.0, 2080.0],
-    [-1.0, -1.0, -1.0, -1.0, 1404.0, 1560.0, 2808.0, 3120.0],
-    [-1.0, -1.0, -1.0, -1.0, 1872.0, 2080.0, 3744.0, 4160.0],
-    [-1.0, -1.0, -1.0, -1.0, 2106.0, 2340.0, 4212.0, 4680.0],
-    [-1.0, -1.0, -1.0, -1.0, 2340.0, 2600.0, 4680.0, 5200.0],
-    [-1.0, -1.0, -1.0, -1.0, 2808.0, 3120.0, 5616.0, 6240.0],
-    [-1.0, -1.0, -1.0, -1.0, 3120.0, 3466.7, 6240.0, 6933.3],
+    [32.5, 36.1, 67.5, 75.0, 146.3, 162.5, 292.5, 325.0],
+    [65.0, 72.2, 135.0, 150.0, 292.5, 325.0, 585.0, 650.0],
+    [97.5, 108.3, 202.5, 225.0, 438.8, 487.5, 877.5, 975.0],
+    [130.0, 144.4, 270.0, 300.0, 585.0, 650.0, 1170.0, 1300.0],
+    [195.0, 216.7, 405.0, 450.0, 877.5, 975.0, 1755.0, 1950.0],
+    [260.0, 288.9, 540.0, 600.0, 1170.0, 1300.0, 2340.0, 2600.0],
+    [292.5, 325.0, 607.5, 675.0, 1316.3, 1462.5, 2632.5, 2925.0],
+    [325.0, 361.1, 675.0, 750.0, 1462.5, 1625.0, 2925.0, 3250.0],
+    [390.0, 433.3, 810.0, 900.0, 1755.0, 1950.0, 3510.0, 3900.0],
+    [-1.0, -1.0, 900.0, 1000.0, 1950.0, 2166.7, 3900.0, 4333.3],
+    [39.0, 43.3, 81.0, 90.0, 175.5, 195.0, 351.0, 390.0],
+    [78.0, 86.7, 162.0, 180.0, 351.0, 390.0, 702.0, 780.0],
+    [117.0, 130.0, 243.0, 270.0, 526.5, 585.0, 1053.0, 1170.0],
+    [156.0, 173.3, 324.0, 360.0, 702.0, 780.0, 1404.0, 1560.0],
+    [234.0, 260.0, 486.0, 540.0, 1053.0, 1170.0, 2106.0, 2340.0],
+    [312.0, 346.7, 648.0, 720.0, 1404.0, 1560.0, 2808.0, 3120.0],
+    [351.0, 390.0, 729.0, 810.0, 1579.5, 1755.0, 3159.0, 3510.0],
+    [390.0, 433.3, 810.0, 900.0, 1755.0, 1950.0, 3510.0, 3900.0],
+    [468.0, 520.0, 972.0, 1080.0, 2106.0, 2340.0, 4212.0, 4680.0],
+    [520.0, 577.8, 1080.0, 1200.0, -1.0, -1.0, 4680.0, 5200.0],
+    [45.5, 50.6, 94.5, 105.0, 204.8, 227.5, 409.5, 455.0],
+    [91.0, 101.1, 189.0, 210.0, 409.5, 455.0, 819.0, 910.0],
+    [136.5, 151.7, 283.5, 315.0, 614.3, 682.5, 1228.5, 1365.0],
+    [182.0, 202.2, 378.0, 420.0, 819.0, 910.0, 1638.0, 1820.0],
+    [273.0, 303.3, 567.0, 630.0, 1228.5, 1365.0, 2457.0, 2730.0],
+    [364.0, 404.4, 756.0, 840.0, 1638.0, 1820.0, 3276.0, 3640.0],
+    [409.5, 455.0, 850.5, 945.0, -1.0, -1.0, 3685.5, 4095.0],
+    [455.0, 505.6, 945.0, 1050.0, 2047.5, 2275.0, 4095.0, 4550.0],
+    [546.0, 606.7, 1134.0, 1260.0, 2457.0, 2730.0, 4914.0, 5460.0],
+    [-1.0, -1.0, 1260.0, 1400.0, 2730.0, 3033.3, 5460.0, 6066.7],
+    [52.0, 57.8, 108.0, 120.0, 234.0, 260.0, 468.0, 520.0],
+    [104.0, 115.6, 216.0, 240.0, 468.0, 520.0, 936.0, 1040.0],
+    [156.0, 173.3, 324.0, 360.0, 702.0, 780.0, 1404.0, 1560.0],
+    [208.0, 231.1, 432.0, 480.0, 936.0, 1040.0, 1872.0, 2080.0],
+    [312.0, 346.7, 648.0, 720.0, 1404.0, 1560.0, 2808.0, 3120.0],
+    [416.0, 462.2, 864.0, 960.0, 1872.0, 2080.0, 3744.0, 4160.0],
+    [468.0, 520.0, 972.0, 1080.0, 2106.0, 2340.0, 4212.0, 4680.0],
+    [520.0, 577.8, 1080.0, 1200.0, 2340.0, 2600.0, 4680.0, 5200.0],
+    [624.0, 693.3, 1296.0, 1440.0, 2808.0, 3120.0, 5616.0, 6240.0],
+    [-1.0, -1.0, 1440.0, 1600.0, 3120.0, 3466.7, 6240.0, 6933.3],
 ];
 
 /// Returns the 802.11n data rate based on the MCS index, bandwidth, and guard
-/// interval.
+/// interval. The rate is for the occupied bandwidth, so 20L and 20U use the
+/// 20 MHz rate.
+///
+/// All MCS indexes 0 - 76 are supported, including the unequal modulation
+/// indexes 33 - 76. MCS 32 is only valid with a 40 MHz bandwidth.
 pub fn ht_rate(index: u8, bw: Bandwidth, gi: GuardInterval) -> Result<f32> {
-    if index > 31 {
-        return Err(Error::InvalidFormat);
+    if index > 76 {
+        return Err(Error::invalid_format());
     }
 
-    let b = match bw.bandwidth {
+    let b = match bw.occupied() {
         20 => 0,
         40 => 2,
-        _ => return Err(Error::InvalidFormat),
+        _ => return Err(Error::invalid_format()),
     };
 
     let col = b + (if gi == GuardInterval::Short { 1 } else { 0 });
 
-    Ok(HT_RATE[index as usize][col])
+    let rate = HT_RATE[index as usize][col];
+    if rate < 0.0 {
+        return Err(Error::invalid_format());
+    }
+
+    Ok(rate)
 }
 
 /// Returns the 802.11ac data rate based on the MCS index, bandwidth, guard
 /// interval, and number of spatial streams.
+///
+/// Combinations that are not allowed by the standard, such as MCS 9 at 20 MHz
+/// with a single spatial stream, return an error.
 pub fn vht_rate(index: u8, bw: Bandwidth, gi: GuardInterval, nss: u8) -> Result<f32> {
-    if index > 9 || nss > 8 {
-        return Err(Error::InvalidFormat);
+    if index > 9 || nss == 0 || nss > 8 {
+        return Err(Error::invalid_format());
     }
 
-    let b = match bw.bandwidth {
+    let b = match bw.occupied() {
         20 => 0,
         40 => 2,
         80 => 4,
         160 => 6,
-        _ => return Err(Error::InvalidFormat),
+        _ => return Err(Error::invalid_format()),
     };
 
     let col = b + (if gi == GuardInterval::Short { 1 } else { 0 });
@@ -160,14 +219,136 @@ pub fn vht_rate(index: u8, bw: Bandwidth, gi: GuardInterval, nss: u8) -> Result<
 
     let rate = VHT_RATE[row as usize][col];
     if rate < 0.0 {
-        return Err(Error::InvalidFormat);
+        return Err(Error::invalid_format());
     }
 
     Ok(rate)
 }
 
+/// A datarate rounded to 0.1 Mbps, to use datarates as map keys.
+///
+/// The datarates are `f32` so the fields containing them can't be `Eq` or
+/// `Hash`, while the rate tables only differ in rounding.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateKey(pub u32);
+
+impl RateKey {
+    /// Returns the key of a datarate in Mbps, negative datarates are 0.
+    pub fn new(mbps: f32) -> RateKey {
+        RateKey((mbps * 10.0 + 0.5) as u32)
+    }
+
+    /// Returns the rounded datarate in Mbps.
+    pub fn mbps(self) -> f32 {
+        self.0 as f32 / 10.0
+    }
+}
+
+/// A frequency band.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Band {
+    /// 2.4 GHz.
+    GHz2,
+    /// 5 GHz.
+    GHz5,
+    /// 6 GHz.
+    GHz6,
+}
+
+/// The PHY that transmitted a frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhyMode {
+    /// Direct Sequence Spread Spectrum (DSSS), 1 and 2 Mbps.
+    DSSS,
+    /// Complementary Code Keying (CCK), 5.5 and 11 Mbps.
+    CCK,
+    /// Orthogonal Frequency-Division Multiplexing (OFDM), 802.11a/g.
+    OFDM,
+    /// High Throughput (HT), 802.11n.
+    HT,
+    /// Very High Throughput (VHT), 802.11ac.
+    VHT,
+    /// High Efficiency (HE), 802.11ax.
+    HE,
+    /// Extremely High Throughput (EHT), 802.11be.
+    EHT,
+}
+
+/// The field describing the PHY parameters of a frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhyField {
+    /// The legacy [Rate](../struct.Rate.html).
+    Legacy,
+    /// The [MCS](../struct.MCS.html) of an HT frame.
+    MCS,
+    /// The [VHT](../struct.VHT.html) field.
+    VHT,
+    /// The [HE](../struct.HE.html) field.
+    HE,
+    /// The [U-SIG](../struct.USIG.html) or [EHT](../struct.EHT.html) field.
+    EHT,
+}
+
+/// The time of a frame by the MAC clock, see
+/// [Radiotap::mac_time](../../struct.Radiotap.html#method.mac_time).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MacTime {
+    /// The [TSFT](../struct.TSFT.html) in microseconds.
+    TSFT(u64),
+    /// The [Timestamp](../struct.Timestamp.html) converted to microseconds.
+    Timestamp(u64),
+}
+
+impl MacTime {
+    /// Returns the time in microseconds.
+    pub fn micros(self) -> u64 {
+        match self {
+            MacTime::TSFT(micros) | MacTime::Timestamp(micros) => micros,
+        }
+    }
+}
+
+/// Returns the center frequency in MHz of the IEEE 802.11 channel in the given
+/// band, or `None` if the channel is not in the band.
+pub fn channel_to_freq(channel: u16, band: Band) -> Option<u16> {
+    match (band, channel) {
+        (Band::GHz2, 14) => Some(2484),
+        (Band::GHz2, 1..=13) => Some(2407 + channel * 5),
+        (Band::GHz5, 1..=189) => Some(5000 + channel * 5),
+        (Band::GHz6, 2) => Some(5935),
+        // Only odd channels are used in the 6 GHz band, except channel 2
+        (Band::GHz6, 1..=233) if channel % 2 == 1 => Some(5950 + channel * 5),
+        _ => None,
+    }
+}
+
+/// Returns the IEEE 802.11 channel number of the center frequency in MHz in the
+/// given band, or `None` if the frequency is not a channel in the band. This
+/// is the inverse of [channel_to_freq](fn.channel_to_freq.html).
+pub fn freq_to_channel(freq: u16, band: Band) -> Option<u16> {
+    let channel = match band {
+        Band::GHz2 if freq == 2484 => 14,
+        Band::GHz2 => freq.checked_sub(2407)? / 5,
+        Band::GHz5 => freq.checked_sub(5000)? / 5,
+        Band::GHz6 if freq == 5935 => 2,
+        Band::GHz6 => freq.checked_sub(5950)? / 5,
+    };
+
+    if channel_to_freq(channel, band) == Some(freq) {
+        Some(channel)
+    } else {
+        None
+    }
+}
+
 /// Flags describing the channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChannelFlags {
     /// Turbo channel.
     pub turbo: bool,
@@ -189,6 +370,7 @@ pub struct ChannelFlags {
 
 /// Extended flags describing the channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XChannelFlags {
     /// Turbo channel.
     pub turbo: bool,
@@ -223,17 +405,28 @@ pub struct XChannelFlags {
 }
 
 /// Struct containing the bandwidth, sideband, and sideband index.
+///
+/// A sideband means the frame only occupies part of the channel, for example
+/// VHT code 5 is a 40 MHz frame in the lower half of an 80 MHz channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bandwidth {
-    /// The bandwidth in MHz.
+    /// The channel bandwidth in MHz.
     pub bandwidth: u8,
-    /// The sideband bandwidth in MHz.
+    /// The bandwidth in MHz of the part of the channel the frame occupies.
     pub sideband: Option<u8>,
-    /// The sideband index.
+    /// The index of the occupied part of the channel, starting from the lowest
+    /// frequency.
     pub sideband_index: Option<u8>,
 }
 
 impl Bandwidth {
+    /// Returns the bandwidth for the given VHT bandwidth code, which also
+    /// covers the HT codes 0 to 3. Codes 2 and 3 are the lower and upper
+    /// 20 MHz of a 40 MHz channel (20L and 20U), given by the sideband.
+    ///
+    /// Codes 26 to 31 are reserved and, like any larger value, return an
+    /// `InvalidFormat` error carrying the code.
     pub fn new(value: u8) -> Result<Bandwidth> {
         let (bandwidth, sideband, sideband_index) = match value {
             0 => (20, None, None),
@@ -262,9 +455,8 @@ impl Bandwidth {
             23 => (160, Some(20), Some(5)),
             24 => (160, Some(20), Some(6)),
             25 => (160, Some(20), Some(7)),
-            _ => {
-                return Err(Error::InvalidFormat);
-            }
+            // Reserved, or too large for the 5 bit code
+            _ => return Err(Error::reserved_value(value)),
         };
         Ok(Bandwidth {
             bandwidth,
@@ -272,11 +464,78 @@ impl Bandwidth {
             sideband_index,
         })
     }
+
+    /// Returns the raw value of the bandwidth, or `None` if it has no raw
+    /// value.
+    pub fn value(self) -> Option<u8> {
+        (0..=25).find(|&value| Bandwidth::new(value).ok() == Some(self))
+    }
+
+    /// Returns the channel bandwidth in MHz, 80+80 MHz is 160 MHz.
+    pub fn mhz(self) -> u16 {
+        u16::from(self.bandwidth)
+    }
+
+    /// Returns the bandwidth in MHz occupied by the frame, which is the
+    /// sideband if present.
+    pub fn occupied(self) -> u8 {
+        self.sideband.unwrap_or(self.bandwidth)
+    }
+
+    /// Returns the offset in MHz of the occupied part from the lowest
+    /// frequency of the channel, 0 when the whole channel is occupied.
+    pub fn sideband_offset(self) -> u16 {
+        match (self.sideband, self.sideband_index) {
+            (Some(sideband), Some(index)) => u16::from(sideband) * u16::from(index),
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Bandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} MHz", self.bandwidth)
+    }
+}
+
+impl VHTUser {
+    /// Returns whether the users are equal, with datarates compared within
+    /// the given tolerance in Mbps.
+    pub fn approx_eq(&self, other: &VHTUser, epsilon: f32) -> bool {
+        VHTUser {
+            datarate: None,
+            ..*self
+        } == VHTUser {
+            datarate: None,
+            ..*other
+        } && datarate_approx_eq(self.datarate, other.datarate, epsilon)
+    }
+}
+
+/// Returns whether both datarates are unknown or within `epsilon` Mbps of
+/// each other.
+pub(crate) fn datarate_approx_eq(a: Option<f32>, b: Option<f32>, epsilon: f32) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a - b <= epsilon && b - a <= epsilon,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+impl fmt::Display for VHTUser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MCS {} NSS {}", self.index, self.nss)?;
+        if let Some(datarate) = self.datarate {
+            write!(f, " ({:.1} Mbps)", datarate)?;
+        }
+        Ok(())
+    }
 }
 
 /// Represents a [VHT](../struct.VHT.html) user, the [VHT](../struct.VHT.html)
 /// encodes the MCS and NSS for up to four users.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VHTUser {
     /// The 802.11ac MCS index.
     pub index: u8,
@@ -284,14 +543,120 @@ pub struct VHTUser {
     pub fec: FEC,
     /// Number of spatial streams (range 1 - 8).
     pub nss: u8,
-    /// Number of space-time streams (range 1 - 16).
+    /// Number of space-time streams (range 1 - 16), twice `nss` if the
+    /// [VHT](../struct.VHT.html) has STBC, otherwise equal to `nss`.
     pub nsts: u8,
     /// The datarate in Mbps
     pub datarate: Option<f32>,
 }
 
+/// Represents an [EHT](../struct.EHT.html) user. Unknown sub-fields are
+/// `None`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EHTUser {
+    /// The STA ID.
+    pub sta_id: Option<u16>,
+    /// The EHT-MCS index.
+    pub index: Option<u8>,
+    /// The FEC type.
+    pub fec: Option<FEC>,
+    /// Number of spatial streams, for non MU-MIMO users.
+    pub nss: Option<u8>,
+    /// Whether transmit beamforming is used, for non MU-MIMO users.
+    pub beamformed: Option<bool>,
+    /// The spatial configuration, for MU-MIMO users.
+    pub spatial_config: Option<u8>,
+    /// Whether the captured data is for this user.
+    pub data_for_user: bool,
+}
+
+impl EHTUser {
+    /// Returns the user parsed from a user info word.
+    pub fn new(info: u32) -> EHTUser {
+        let known = |flag| info & flag != 0;
+        EHTUser {
+            sta_id: if known(0x0000_0001) {
+                Some(((info & 0x0007_ff00) >> 8) as u16)
+            } else {
+                None
+            },
+            index: if known(0x0000_0002) {
+                Some(((info & 0x00f0_0000) >> 20) as u8)
+            } else {
+                None
+            },
+            fec: if !known(0x0000_0004) {
+                None
+            } else if known(0x0008_0000) {
+                Some(FEC::LDPC)
+            } else {
+                Some(FEC::BCC)
+            },
+            nss: if known(0x0000_0010) {
+                Some(((info & 0x0f00_0000) >> 24) as u8)
+            } else {
+                None
+            },
+            beamformed: if known(0x0000_0020) {
+                Some(known(0x2000_0000))
+            } else {
+                None
+            },
+            spatial_config: if known(0x0000_0040) {
+                Some(((info & 0x3f00_0000) >> 24) as u8)
+            } else {
+                None
+            },
+            data_for_user: known(0x0000_0080),
+        }
+    }
+
+    /// Returns the raw user info word.
+    pub fn value(self) -> u32 {
+        let mut info = 0;
+
+        if let Some(sta_id) = self.sta_id {
+            info |= 0x0000_0001 | (u32::from(sta_id) & 0x07ff) << 8;
+        }
+
+        if let Some(index) = self.index {
+            info |= 0x0000_0002 | (u32::from(index) & 0x0f) << 20;
+        }
+
+        if let Some(fec) = self.fec {
+            info |= 0x0000_0004;
+            if fec == FEC::LDPC {
+                info |= 0x0008_0000;
+            }
+        }
+
+        if let Some(nss) = self.nss {
+            info |= 0x0000_0010 | (u32::from(nss) & 0x0f) << 24;
+        }
+
+        if let Some(beamformed) = self.beamformed {
+            info |= 0x0000_0020;
+            if beamformed {
+                info |= 0x2000_0000;
+            }
+        }
+
+        if let Some(spatial_config) = self.spatial_config {
+            info |= 0x0000_0040 | (u32::from(spatial_config) & 0x3f) << 24;
+        }
+
+        if self.data_for_user {
+            info |= 0x0000_0080;
+        }
+
+        info
+    }
+}
+
 /// The guard interval.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GuardInterval {
     /// 800 ns.
     Long,
@@ -299,8 +664,28 @@ pub enum GuardInterval {
     Short,
 }
 
+impl GuardInterval {
+    /// Returns the length of the guard interval in nanoseconds.
+    pub fn nanos(self) -> u16 {
+        match self {
+            GuardInterval::Long => 800,
+            GuardInterval::Short => 400,
+        }
+    }
+}
+
+impl fmt::Display for GuardInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            GuardInterval::Long => "long GI",
+            GuardInterval::Short => "short GI",
+        })
+    }
+}
+
 /// Forward error correction type.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FEC {
     /// Binary convolutional coding.
     BCC,
@@ -310,13 +695,297 @@ pub enum FEC {
 
 /// The HT format.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HTFormat {
     Mixed,
     Greenfield,
 }
 
+/// The HE PPDU format.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HEFormat {
+    SU,
+    ExtendedSU,
+    MU,
+    Trigger,
+}
+
+impl HEFormat {
+    pub fn new(value: u8) -> Result<HEFormat> {
+        Ok(match value {
+            0 => HEFormat::SU,
+            1 => HEFormat::ExtendedSU,
+            2 => HEFormat::MU,
+            3 => HEFormat::Trigger,
+            _ => {
+                return Err(Error::invalid_format());
+            }
+        })
+    }
+
+    /// Returns the raw value of the HE format.
+    pub fn value(self) -> u8 {
+        match self {
+            HEFormat::SU => 0,
+            HEFormat::ExtendedSU => 1,
+            HEFormat::MU => 2,
+            HEFormat::Trigger => 3,
+        }
+    }
+}
+
+/// The bandwidth or resource unit allocation of an [HE](../struct.HE.html)
+/// frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HEBandwidth {
+    /// 20 MHz.
+    BW20,
+    /// 40 MHz.
+    BW40,
+    /// 80 MHz.
+    BW80,
+    /// 160 MHz or 80+80 MHz.
+    BW160,
+    /// 26-tone RU.
+    RU26,
+    /// 52-tone RU.
+    RU52,
+    /// 106-tone RU.
+    RU106,
+    /// 242-tone RU.
+    RU242,
+    /// 484-tone RU.
+    RU484,
+    /// 996-tone RU.
+    RU996,
+    /// 2x996-tone RU.
+    RU2x996,
+}
+
+impl HEBandwidth {
+    pub fn new(value: u8) -> Result<HEBandwidth> {
+        Ok(match value {
+            0 => HEBandwidth::BW20,
+            1 => HEBandwidth::BW40,
+            2 => HEBandwidth::BW80,
+            3 => HEBandwidth::BW160,
+            4 => HEBandwidth::RU26,
+            5 => HEBandwidth::RU52,
+            6 => HEBandwidth::RU106,
+            7 => HEBandwidth::RU242,
+            8 => HEBandwidth::RU484,
+            9 => HEBandwidth::RU996,
+            10 => HEBandwidth::RU2x996,
+            _ => {
+                return Err(Error::invalid_format());
+            }
+        })
+    }
+
+    /// Returns the raw value of the bandwidth.
+    pub fn value(self) -> u8 {
+        match self {
+            HEBandwidth::BW20 => 0,
+            HEBandwidth::BW40 => 1,
+            HEBandwidth::BW80 => 2,
+            HEBandwidth::BW160 => 3,
+            HEBandwidth::RU26 => 4,
+            HEBandwidth::RU52 => 5,
+            HEBandwidth::RU106 => 6,
+            HEBandwidth::RU242 => 7,
+            HEBandwidth::RU484 => 8,
+            HEBandwidth::RU996 => 9,
+            HEBandwidth::RU2x996 => 10,
+        }
+    }
+
+    /// Returns the bandwidth in MHz, 80+80 MHz is 160 MHz. The bandwidth of
+    /// the RUs smaller than 242 tones is rounded down.
+    pub fn mhz(self) -> u16 {
+        match self {
+            HEBandwidth::RU26 => 2,
+            HEBandwidth::RU52 => 4,
+            HEBandwidth::RU106 => 8,
+            HEBandwidth::BW20 | HEBandwidth::RU242 => 20,
+            HEBandwidth::BW40 | HEBandwidth::RU484 => 40,
+            HEBandwidth::BW80 | HEBandwidth::RU996 => 80,
+            HEBandwidth::BW160 | HEBandwidth::RU2x996 => 160,
+        }
+    }
+}
+
+/// The bandwidth of an 802.11be frame, given in the [USIG](../struct.USIG.html)
+/// field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EHTBandwidth {
+    /// 20 MHz.
+    BW20,
+    /// 40 MHz.
+    BW40,
+    /// 80 MHz.
+    BW80,
+    /// 160 MHz.
+    BW160,
+    /// 320 MHz, channelization 1.
+    BW320Ch1,
+    /// 320 MHz, channelization 2.
+    BW320Ch2,
+}
+
+impl EHTBandwidth {
+    pub fn new(value: u8) -> Result<EHTBandwidth> {
+        Ok(match value {
+            0 => EHTBandwidth::BW20,
+            1 => EHTBandwidth::BW40,
+            2 => EHTBandwidth::BW80,
+            3 => EHTBandwidth::BW160,
+            4 => EHTBandwidth::BW320Ch1,
+            5 => EHTBandwidth::BW320Ch2,
+            _ => {
+                return Err(Error::invalid_format());
+            }
+        })
+    }
+
+    /// Returns the raw value of the bandwidth.
+    pub fn value(self) -> u8 {
+        match self {
+            EHTBandwidth::BW20 => 0,
+            EHTBandwidth::BW40 => 1,
+            EHTBandwidth::BW80 => 2,
+            EHTBandwidth::BW160 => 3,
+            EHTBandwidth::BW320Ch1 => 4,
+            EHTBandwidth::BW320Ch2 => 5,
+        }
+    }
+
+    /// Returns the bandwidth in MHz.
+    pub fn mhz(self) -> u16 {
+        match self {
+            EHTBandwidth::BW20 => 20,
+            EHTBandwidth::BW40 => 40,
+            EHTBandwidth::BW80 => 80,
+            EHTBandwidth::BW160 => 160,
+            EHTBandwidth::BW320Ch1 | EHTBandwidth::BW320Ch2 => 320,
+        }
+    }
+}
+
+/// The guard interval of an [HE](../struct.HE.html) frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HEGuardInterval {
+    /// 800 ns.
+    Ns800,
+    /// 1600 ns.
+    Ns1600,
+    /// 3200 ns.
+    Ns3200,
+}
+
+impl HEGuardInterval {
+    pub fn new(value: u8) -> Result<HEGuardInterval> {
+        Ok(match value {
+            0 => HEGuardInterval::Ns800,
+            1 => HEGuardInterval::Ns1600,
+            2 => HEGuardInterval::Ns3200,
+            _ => {
+                return Err(Error::invalid_format());
+            }
+        })
+    }
+
+    /// Returns the raw value of the guard interval.
+    pub fn value(self) -> u8 {
+        match self {
+            HEGuardInterval::Ns800 => 0,
+            HEGuardInterval::Ns1600 => 1,
+            HEGuardInterval::Ns3200 => 2,
+        }
+    }
+
+    /// Returns the length of the guard interval in nanoseconds.
+    pub fn nanos(self) -> u16 {
+        match self {
+            HEGuardInterval::Ns800 => 800,
+            HEGuardInterval::Ns1600 => 1600,
+            HEGuardInterval::Ns3200 => 3200,
+        }
+    }
+}
+
+/// The HE-LTF symbol size.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LTFSize {
+    X1,
+    X2,
+    X4,
+}
+
+impl LTFSize {
+    pub fn new(value: u8) -> Result<LTFSize> {
+        Ok(match value {
+            1 => LTFSize::X1,
+            2 => LTFSize::X2,
+            3 => LTFSize::X4,
+            _ => {
+                return Err(Error::invalid_format());
+            }
+        })
+    }
+
+    /// Returns the raw value of the LTF size.
+    pub fn value(self) -> u8 {
+        match self {
+            LTFSize::X1 => 1,
+            LTFSize::X2 => 2,
+            LTFSize::X4 => 3,
+        }
+    }
+}
+
+/// The type of a [ZeroLengthPSDU](../struct.ZeroLengthPSDU.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PSDUType {
+    /// A sounding PPDU.
+    Sounding,
+    /// The data was not captured, e.g. multiple users.
+    NotCaptured,
+    /// Vendor-specific.
+    Vendor,
+    /// A reserved value.
+    Reserved(u8),
+}
+
+impl PSDUType {
+    pub fn new(value: u8) -> PSDUType {
+        match value {
+            0 => PSDUType::Sounding,
+            1 => PSDUType::NotCaptured,
+            0xff => PSDUType::Vendor,
+            value => PSDUType::Reserved(value),
+        }
+    }
+
+    /// Returns the raw value of the PSDU type.
+    pub fn value(self) -> u8 {
+        match self {
+            PSDUType::Sounding => 0,
+            PSDUType::NotCaptured => 1,
+            PSDUType::Vendor => 0xff,
+            PSDUType::Reserved(value) => value,
+        }
+    }
+}
+
 /// The time unit of the [Timestamp](../struct.Timestamp.html).
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeUnit {
     Milliseconds,
     Microseconds,
@@ -330,14 +999,24 @@ impl TimeUnit {
             1 => TimeUnit::Microseconds,
             2 => TimeUnit::Nanoseconds,
             _ => {
-                return Err(Error::InvalidFormat);
+                return Err(Error::invalid_format());
             }
         })
     }
+
+    /// Returns the raw value of the time unit.
+    pub fn value(self) -> u8 {
+        match self {
+            TimeUnit::Milliseconds => 0,
+            TimeUnit::Microseconds => 1,
+            TimeUnit::Nanoseconds => 2,
+        }
+    }
 }
 
 /// The sampling position of the [Timestamp](../struct.Timestamp.html).
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplingPosition {
     StartMPDU,
     StartPLCP,
@@ -354,7 +1033,18 @@ impl SamplingPosition {
             2 => SamplingPosition::EndPPDU,
             3 => SamplingPosition::EndMPDU,
             15 => SamplingPosition::Unknown,
-            _ => return Err(Error::InvalidFormat),
+            _ => return Err(Error::invalid_format()),
         })
     }
+
+    /// Returns the raw value of the sampling position.
+    pub fn value(self) -> u8 {
+        match self {
+            SamplingPosition::StartMPDU => 0,
+            SamplingPosition::StartPLCP => 1,
+            SamplingPosition::EndPPDU => 2,
+            SamplingPosition::EndMPDU => 3,
+            SamplingPosition::Unknown => 15,
+        }
+    }
 }
@@ -2,16 +2,19 @@
 
 pub mod ext;
 
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt;
+
 use bitops::BitOps;
-use byteorder::{ReadBytesExt, LE};
-use std::io::{Cursor, Read};
 
-use crate::{field::ext::*, Error, Result};
+use crate::{field::ext::*, reader::ByteReader, Error, Result};
 
-type OUI = [u8; 3];
+/// An Organizationally Unique Identifier, identifying a vendor namespace.
+pub type Oui = [u8; 3];
 
 /// The type of Radiotap field.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Kind {
     TSFT,
     Flags,
@@ -36,10 +39,31 @@ pub enum Kind {
     AMPDUStatus,
     VHT,
     Timestamp,
+    HE,
+    HEMU,
+    ZeroLengthPSDU,
+    LSIG,
+    /// The U-SIG field, only given as a TLV field.
+    USIG,
+    /// The EHT field, only given as a TLV field.
+    EHT,
     VendorNamespace(Option<VendorNamespace>),
+    /// A field in a vendor namespace, with its present bit.
+    VendorField(u8),
+    /// A TLV field, with its type.
+    TLV(u16),
+    /// A standard field that is not parsed, with its present bit. Its layout
+    /// is known so the fields after it can still be located.
+    Unparsed(u8),
 }
 
 impl Kind {
+    /// Returns the kind of the field with the given present bit in the
+    /// radiotap namespace, the bits of each further present word follow on
+    /// from 32.
+    ///
+    /// No fields are defined past bit 27, newer fields are only given as TLV
+    /// fields. Returns `UnsupportedField` if the bit is not a known field.
     pub fn new(value: u8) -> Result<Kind> {
         Ok(match value {
             0 => Kind::TSFT,
@@ -65,17 +89,43 @@ impl Kind {
             20 => Kind::AMPDUStatus,
             21 => Kind::VHT,
             22 => Kind::Timestamp,
+            23 => Kind::HE,
+            24 => Kind::HEMU,
+            26 => Kind::ZeroLengthPSDU,
+            27 => Kind::LSIG,
+            value if unparsed_layout(value).is_some() => Kind::Unparsed(value),
             _ => {
                 return Err(Error::UnsupportedField);
             }
         })
     }
 
+    /// Returns the kind of a TLV field from its TLV type. Fields without a
+    /// present bit are known by their TLV type, all others are
+    /// `Kind::TLV(tlv_type)`.
+    pub fn from_tlv(tlv_type: u16) -> Kind {
+        match tlv_type {
+            33 => Kind::USIG,
+            34 => Kind::EHT,
+            tlv_type => Kind::TLV(tlv_type),
+        }
+    }
+
+    /// Returns the TLV type of the field, or `None` if it is not a TLV field.
+    pub fn tlv_type(self) -> Option<u16> {
+        match self {
+            Kind::USIG => Some(33),
+            Kind::EHT => Some(34),
+            Kind::TLV(tlv_type) => Some(tlv_type),
+            _ => None,
+        }
+    }
+
     /// Returns the align value for the field.
     pub fn align(self) -> u64 {
         match self {
             Kind::TSFT | Kind::Timestamp => 8,
-            Kind::XChannel | Kind::AMPDUStatus => 4,
+            Kind::XChannel | Kind::AMPDUStatus | Kind::USIG | Kind::EHT | Kind::TLV(_) => 4,
             Kind::Channel
             | Kind::FHSS
             | Kind::LockQuality
@@ -84,18 +134,60 @@ impl Kind {
             | Kind::RxFlags
             | Kind::TxFlags
             | Kind::VHT
+            | Kind::HE
+            | Kind::HEMU
+            | Kind::LSIG
             | Kind::VendorNamespace(_) => 2,
+            Kind::Unparsed(bit) => unparsed_layout(bit).map_or(1, |(align, _)| align),
             _ => 1,
         }
     }
 
+    /// Returns the present bit of the field.
+    pub fn bit(self) -> u8 {
+        match self {
+            Kind::TSFT => 0,
+            Kind::Flags => 1,
+            Kind::Rate => 2,
+            Kind::Channel => 3,
+            Kind::FHSS => 4,
+            Kind::AntennaSignal => 5,
+            Kind::AntennaNoise => 6,
+            Kind::LockQuality => 7,
+            Kind::TxAttenuation => 8,
+            Kind::TxAttenuationDb => 9,
+            Kind::TxPower => 10,
+            Kind::Antenna => 11,
+            Kind::AntennaSignalDb => 12,
+            Kind::AntennaNoiseDb => 13,
+            Kind::RxFlags => 14,
+            Kind::TxFlags => 15,
+            Kind::RTSRetries => 16,
+            Kind::DataRetries => 17,
+            Kind::XChannel => 18,
+            Kind::MCS => 19,
+            Kind::AMPDUStatus => 20,
+            Kind::VHT => 21,
+            Kind::Timestamp => 22,
+            Kind::HE => 23,
+            Kind::HEMU => 24,
+            Kind::ZeroLengthPSDU => 26,
+            Kind::LSIG => 27,
+            Kind::VendorNamespace(_) => 30,
+            Kind::VendorField(bit) | Kind::Unparsed(bit) => bit,
+            // TLV fields are indicated by the TLV bit
+            Kind::USIG | Kind::EHT | Kind::TLV(_) => 28,
+        }
+    }
+
     /// Returns the size of the field.
     pub fn size(self) -> usize {
         match self {
-            Kind::VHT | Kind::Timestamp => 12,
+            Kind::VHT | Kind::Timestamp | Kind::HE | Kind::HEMU => 12,
             Kind::TSFT | Kind::AMPDUStatus | Kind::XChannel => 8,
             Kind::VendorNamespace(_) => 6,
-            Kind::Channel => 4,
+            // The size of a TLV field is given in its own TLV header
+            Kind::Channel | Kind::LSIG | Kind::USIG | Kind::EHT | Kind::TLV(_) => 4,
             Kind::MCS => 3,
             Kind::FHSS
             | Kind::LockQuality
@@ -103,23 +195,224 @@ impl Kind {
             | Kind::TxAttenuationDb
             | Kind::RxFlags
             | Kind::TxFlags => 2,
+            Kind::Unparsed(bit) => unparsed_layout(bit).map_or(0, |(_, size)| size),
             _ => 1,
         }
     }
+
+    /// Returns the name of the field on [radiotap.org](http://www.radiotap.org/fields/defined).
+    /// Vendor fields, unknown TLV fields and unknown standard fields share a
+    /// name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::TSFT => "TSFT",
+            Kind::Flags => "Flags",
+            Kind::Rate => "Rate",
+            Kind::Channel => "Channel",
+            Kind::FHSS => "FHSS",
+            Kind::AntennaSignal => "Antenna signal",
+            Kind::AntennaNoise => "Antenna noise",
+            Kind::LockQuality => "Lock quality",
+            Kind::TxAttenuation => "TX attenuation",
+            Kind::TxAttenuationDb => "dB TX attenuation",
+            Kind::TxPower => "dBm TX power",
+            Kind::Antenna => "Antenna",
+            Kind::AntennaSignalDb => "dB antenna signal",
+            Kind::AntennaNoiseDb => "dB antenna noise",
+            Kind::RxFlags => "RX flags",
+            Kind::TxFlags => "TX flags",
+            Kind::RTSRetries => "RTS retries",
+            Kind::DataRetries => "data retries",
+            Kind::XChannel => "XChannel",
+            Kind::MCS => "MCS",
+            Kind::AMPDUStatus => "A-MPDU status",
+            Kind::VHT => "VHT",
+            Kind::Timestamp => "timestamp",
+            Kind::HE => "HE",
+            Kind::HEMU => "HE-MU",
+            Kind::ZeroLengthPSDU => "0-length-PSDU",
+            Kind::LSIG => "L-SIG",
+            Kind::USIG => "U-SIG",
+            Kind::EHT => "EHT",
+            Kind::VendorNamespace(_) => "Vendor Namespace",
+            Kind::VendorField(_) => "vendor field",
+            Kind::TLV(_) => "TLV",
+            Kind::Unparsed(25) => "HE-MU-other-user",
+            Kind::Unparsed(_) => "unknown",
+        }
+    }
+}
+
+impl core::str::FromStr for Kind {
+    type Err = Error;
+
+    /// Returns the kind with the given [name](enum.Kind.html#method.name).
+    /// A vendor namespace is `Kind::VendorNamespace(None)`. Returns
+    /// `UnsupportedField` for the names shared by several kinds.
+    fn from_str(name: &str) -> Result<Kind> {
+        Ok(match name {
+            "TSFT" => Kind::TSFT,
+            "Flags" => Kind::Flags,
+            "Rate" => Kind::Rate,
+            "Channel" => Kind::Channel,
+            "FHSS" => Kind::FHSS,
+            "Antenna signal" => Kind::AntennaSignal,
+            "Antenna noise" => Kind::AntennaNoise,
+            "Lock quality" => Kind::LockQuality,
+            "TX attenuation" => Kind::TxAttenuation,
+            "dB TX attenuation" => Kind::TxAttenuationDb,
+            "dBm TX power" => Kind::TxPower,
+            "Antenna" => Kind::Antenna,
+            "dB antenna signal" => Kind::AntennaSignalDb,
+            "dB antenna noise" => Kind::AntennaNoiseDb,
+            "RX flags" => Kind::RxFlags,
+            "TX flags" => Kind::TxFlags,
+            "RTS retries" => Kind::RTSRetries,
+            "data retries" => Kind::DataRetries,
+            "XChannel" => Kind::XChannel,
+            "MCS" => Kind::MCS,
+            "A-MPDU status" => Kind::AMPDUStatus,
+            "VHT" => Kind::VHT,
+            "timestamp" => Kind::Timestamp,
+            "HE" => Kind::HE,
+            "HE-MU" => Kind::HEMU,
+            "0-length-PSDU" => Kind::ZeroLengthPSDU,
+            "L-SIG" => Kind::LSIG,
+            "U-SIG" => Kind::USIG,
+            "EHT" => Kind::EHT,
+            "Vendor Namespace" => Kind::VendorNamespace(None),
+            "HE-MU-other-user" => Kind::Unparsed(25),
+            _ => {
+                return Err(Error::UnsupportedField);
+            }
+        })
+    }
+}
+
+/// Returns the align and size of the standard fields that are not parsed.
+fn unparsed_layout(bit: u8) -> Option<(u64, usize)> {
+    match bit {
+        // HE-MU-other-user
+        25 => Some((2, 6)),
+        _ => None,
+    }
 }
 
 pub trait Field {
     fn from_bytes(input: &[u8]) -> Result<Self>
     where
         Self: Sized;
+
+    /// Returns the field encoded in its Radiotap representation.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Returns the size in bytes of the field, or `None` if it varies. The
+    /// [from_bytes](fn.from_bytes.html) function checks the input is exactly
+    /// this size.
+    fn size() -> Option<usize>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// The value of any parsed field, see
+/// [Radiotap::field](../struct.Radiotap.html#method.field).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldValue {
+    TSFT(TSFT),
+    Flags(Flags),
+    Rate(Rate),
+    Channel(Channel),
+    FHSS(FHSS),
+    AntennaSignal(AntennaSignal),
+    AntennaNoise(AntennaNoise),
+    LockQuality(LockQuality),
+    TxAttenuation(TxAttenuation),
+    TxAttenuationDb(TxAttenuationDb),
+    TxPower(TxPower),
+    Antenna(Antenna),
+    AntennaSignalDb(AntennaSignalDb),
+    AntennaNoiseDb(AntennaNoiseDb),
+    RxFlags(RxFlags),
+    TxFlags(TxFlags),
+    RTSRetries(RTSRetries),
+    DataRetries(DataRetries),
+    XChannel(XChannel),
+    MCS(MCS),
+    AMPDUStatus(AMPDUStatus),
+    VHT(VHT),
+    Timestamp(Timestamp),
+    HE(HE),
+    HEMU(HEMU),
+    ZeroLengthPSDU(ZeroLengthPSDU),
+    LSIG(LSIG),
+    USIG(USIG),
+    EHT(EHT),
+}
+
+impl FieldValue {
+    /// Returns the kind of the field.
+    pub fn kind(&self) -> Kind {
+        match self {
+            FieldValue::TSFT(_) => Kind::TSFT,
+            FieldValue::Flags(_) => Kind::Flags,
+            FieldValue::Rate(_) => Kind::Rate,
+            FieldValue::Channel(_) => Kind::Channel,
+            FieldValue::FHSS(_) => Kind::FHSS,
+            FieldValue::AntennaSignal(_) => Kind::AntennaSignal,
+            FieldValue::AntennaNoise(_) => Kind::AntennaNoise,
+            FieldValue::LockQuality(_) => Kind::LockQuality,
+            FieldValue::TxAttenuation(_) => Kind::TxAttenuation,
+            FieldValue::TxAttenuationDb(_) => Kind::TxAttenuationDb,
+            FieldValue::TxPower(_) => Kind::TxPower,
+            FieldValue::Antenna(_) => Kind::Antenna,
+            FieldValue::AntennaSignalDb(_) => Kind::AntennaSignalDb,
+            FieldValue::AntennaNoiseDb(_) => Kind::AntennaNoiseDb,
+            FieldValue::RxFlags(_) => Kind::RxFlags,
+            FieldValue::TxFlags(_) => Kind::TxFlags,
+            FieldValue::RTSRetries(_) => Kind::RTSRetries,
+            FieldValue::DataRetries(_) => Kind::DataRetries,
+            FieldValue::XChannel(_) => Kind::XChannel,
+            FieldValue::MCS(_) => Kind::MCS,
+            FieldValue::AMPDUStatus(_) => Kind::AMPDUStatus,
+            FieldValue::VHT(_) => Kind::VHT,
+            FieldValue::Timestamp(_) => Kind::Timestamp,
+            FieldValue::HE(_) => Kind::HE,
+            FieldValue::HEMU(_) => Kind::HEMU,
+            FieldValue::ZeroLengthPSDU(_) => Kind::ZeroLengthPSDU,
+            FieldValue::LSIG(_) => Kind::LSIG,
+            FieldValue::USIG(_) => Kind::USIG,
+            FieldValue::EHT(_) => Kind::EHT,
+        }
+    }
+}
+
+/// Returns the value with all the given flags that are true set.
+fn flags_value<T>(flags: &[(bool, T)]) -> T
+where
+    T: Copy + Default + core::ops::BitOr<Output = T>,
+{
+    flags
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(T::default(), |value, &(_, flag)| value | flag)
 }
 
 /// Parse any `Field` and return a `Result<T>`.
+///
+/// Returns `InvalidFormat` if the field has a fixed size and the input is not
+/// exactly that size.
 pub fn from_bytes<T>(input: &[u8]) -> Result<T>
 where
     T: Field,
 {
-    T::from_bytes(input)
+    match T::size() {
+        Some(size) if size != input.len() => Err(Error::invalid_format()),
+        _ => T::from_bytes(input),
+    }
 }
 
 /// Parse any `Field` and return a `Result<Some<T>>`.
@@ -127,11 +420,58 @@ pub fn from_bytes_some<T>(input: &[u8]) -> Result<Option<T>>
 where
     T: Field,
 {
-    Ok(Some(T::from_bytes(input)?))
+    Ok(Some(from_bytes(input)?))
+}
+
+/// Returns a Radiotap header with the given present words, for a body of
+/// `body_len` bytes following the header.
+///
+/// The bit indicating another present word follows is set on all but the last
+/// word. Any padding before the first field is part of the body.
+///
+/// ```
+/// use radiotap::{field::encode_header, Radiotap};
+///
+/// // Flags and Rate
+/// let mut capture = encode_header(&[0x06], 2);
+/// capture.extend_from_slice(&[0x10, 0x0c]);
+///
+/// let radiotap = Radiotap::from_bytes(&capture).unwrap();
+/// assert_eq!(radiotap.rate.unwrap().value, 6.0);
+/// ```
+pub fn encode_header(present: &[u32], body_len: usize) -> Vec<u8> {
+    let length = 4 + present.len() * 4 + body_len;
+    let mut output = Vec::with_capacity(length);
+    output.extend_from_slice(&[0, 0]);
+    output.extend_from_slice(&(length as u16).to_le_bytes());
+    for (i, word) in present.iter().enumerate() {
+        let word = if i + 1 < present.len() {
+            word | 0x8000_0000
+        } else {
+            *word
+        };
+        output.extend_from_slice(&word.to_le_bytes());
+    }
+    output
 }
 
+/// The kinds of the fields present in a Radiotap capture.
+///
+/// With the `smallvec` feature enabled the kinds of most captures are stored
+/// inline instead of being allocated.
+#[cfg(not(feature = "smallvec"))]
+pub type Present = Vec<Kind>;
+
+/// The kinds of the fields present in a Radiotap capture.
+///
+/// With the `smallvec` feature enabled the kinds of most captures are stored
+/// inline instead of being allocated.
+#[cfg(feature = "smallvec")]
+pub type Present = smallvec::SmallVec<[Kind; 8]>;
+
 /// The Radiotap header, contained in all Radiotap captures.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     /// The Radiotap version, only version 0 is supported.
     pub version: u8,
@@ -140,12 +480,108 @@ pub struct Header {
     /// The size of the Radiotap header.
     pub size: usize,
     /// The fields present in the Radiotap capture.
-    pub present: Vec<Kind>,
+    pub present: Present,
+    /// Whether TLV fields follow the fields in `present`.
+    pub tlv: bool,
+    /// The bits of the fields in the radiotap namespaces that are present but
+    /// not in `present`, the first unsupported field and every field after it.
+    pub skipped: Vec<u8>,
+    /// The indexes in `present` at which a radiotap namespace is started again
+    /// by bit 29, for the fields repeated in each namespace.
+    pub resets: Vec<usize>,
+}
+
+impl Header {
+    /// Returns the length of the Radiotap capture from the first 4 bytes of the
+    /// input, without parsing the present words. This is the offset of the
+    /// 802.11 frame, or of the next capture in a stream.
+    ///
+    /// Returns `UnsupportedVersion` if the version is not 0 and `InvalidLength`
+    /// if the length can't hold the first present word. The input doesn't
+    /// need to contain the whole capture.
+    pub fn peek_length(input: &[u8]) -> Result<usize> {
+        let mut cursor = ByteReader::new(input);
+
+        if cursor.read_u8()? != 0 {
+            return Err(Error::UnsupportedVersion);
+        }
+        cursor.read_u8()?;
+
+        let length = cursor.read_u16()? as usize;
+        if length < 8 {
+            return Err(Error::InvalidLength);
+        }
+        Ok(length)
+    }
+
+    /// Returns whether a field of the given kind is present, without parsing
+    /// the body of the capture. Any vendor namespace matches
+    /// `Kind::VendorNamespace(None)`.
+    pub fn has_field(&self, kind: Kind) -> bool {
+        match kind {
+            Kind::VendorNamespace(_) => self
+                .present
+                .iter()
+                .any(|kind| matches!(kind, Kind::VendorNamespace(_))),
+            kind => self.present.contains(&kind),
+        }
+    }
+
+    /// Returns the present bits of the radiotap namespaces, including the
+    /// skipped fields.
+    pub fn present_mask(&self) -> PresentMask {
+        let bits = self
+            .present
+            .iter()
+            .filter(|kind| !matches!(kind, Kind::VendorNamespace(_) | Kind::VendorField(_)))
+            .map(|kind| kind.bit())
+            .chain(self.skipped.iter().cloned());
+
+        let mut mask = PresentMask(0);
+        for bit in bits.filter(|&bit| bit < 64) {
+            mask.0 |= 1 << bit;
+        }
+        mask
+    }
+}
+
+/// The present bits of the radiotap namespaces as a bitmask, bit 32 onwards
+/// are from the second present word. Fields repeated in extra radiotap
+/// namespaces set the same bit.
+///
+/// It is displayed as the names of the present fields.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PresentMask(pub u64);
+
+impl PresentMask {
+    /// Returns whether the bit is set.
+    pub fn is_set(self, bit: u8) -> bool {
+        bit < 64 && self.0 & (1 << bit) != 0
+    }
+
+    /// Returns the set bits in increasing order.
+    pub fn iter_bits(self) -> impl Iterator<Item = u8> {
+        (0..64).filter(move |&bit| self.is_set(bit))
+    }
+}
+
+impl fmt::Display for PresentMask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<_> = self
+            .iter_bits()
+            .map(|bit| match Kind::new(bit) {
+                Ok(Kind::Unparsed(_)) | Err(_) => format!("bit {}", bit),
+                Ok(kind) => String::from(kind.name()),
+            })
+            .collect();
+        f.write_str(&names.join(", "))
+    }
 }
 
 impl Field for Header {
     fn from_bytes(input: &[u8]) -> Result<Header> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
 
         let version = cursor.read_u8()?;
         if version != 0 {
@@ -155,28 +591,72 @@ impl Field for Header {
 
         cursor.read_u8()?; // Account for 1 byte padding field
 
-        let length = cursor.read_u16::<LE>()?;
-        if input.len() < length as usize {
+        let length = cursor.read_u16()?;
+        // The length includes at least the first present word
+        if length < 8 || input.len() < length as usize {
             return Err(Error::InvalidLength);
         }
 
         let mut present;
-        let mut present_count = 0;
+        let mut present_count: usize = 0;
         let mut vendor_namespace = false;
-        let mut kinds = Vec::new();
+        let mut unsupported = false;
+        let mut tlv = false;
+        let mut kinds = Present::new();
+        let mut skipped = Vec::new();
+        let mut resets = Vec::new();
 
         loop {
-            present = cursor.read_u32::<LE>()?;
+            // The present words must end within the length
+            if cursor.position() + 4 > length as usize {
+                return Err(Error::InvalidFormat {
+                    offset: cursor.position(),
+                    kind: None,
+                    value: None,
+                });
+            }
+            let offset = cursor.position();
+            present = cursor.read_u32()?;
+
+            // The index of a bit in the namespace, which must fit in a `u8`
+            let index = |bit: u8| {
+                present_count
+                    .checked_mul(32)
+                    .and_then(|start: usize| start.checked_add(usize::from(bit)))
+                    .and_then(|index| core::convert::TryFrom::try_from(index).ok())
+                    .ok_or(Error::InvalidFormat {
+                        offset,
+                        kind: None,
+                        value: None,
+                    })
+            };
 
-            if !vendor_namespace {
+            if vendor_namespace && !unsupported {
                 for bit in 0..29 {
                     if present.is_bit_set(bit) {
-                        match Kind::new(present_count * 32 + bit) {
+                        // The vendor namespace will figure out what this is later
+                        kinds.push(Kind::VendorField(index(bit)?));
+                    }
+                }
+            } else if !vendor_namespace {
+                // Bit 28 is the TLV bit, which isn't a field
+                for bit in 0..28 {
+                    if present.is_bit_set(bit) {
+                        let bit = index(bit)?;
+                        if unsupported {
+                            skipped.push(bit);
+                            continue;
+                        }
+                        match Kind::new(bit) {
                             Ok(kind) => {
                                 kinds.push(kind);
                             }
                             Err(Error::UnsupportedField) => {
-                                // Does not matter, we will just parse the ones we can
+                                // We don't know the size or alignment of this field so
+                                // none of the fields after it can be located, we will
+                                // just parse the ones before it.
+                                unsupported = true;
+                                skipped.push(bit);
                             }
                             Err(e) => return Err(e),
                         }
@@ -184,17 +664,26 @@ impl Field for Header {
                 }
             }
 
-            // Need to move to radiotap namespace
+            // TLV fields follow the fields in the present words
+            if !vendor_namespace && present.is_bit_set(28) {
+                tlv = true;
+            }
+
+            // Need to move to radiotap namespace, this doesn't consume any of the
+            // body, the next present word simply starts again at bit 0
             if present.is_bit_set(29) {
                 present_count = 0;
                 vendor_namespace = false;
+                resets.push(kinds.len());
 
             // Need to move to vendor namespace
             } else if present.is_bit_set(30) {
                 present_count = 0;
                 vendor_namespace = true;
                 // We'll figure out what namespace it is later, just use none
-                kinds.push(Kind::VendorNamespace(None))
+                if !unsupported {
+                    kinds.push(Kind::VendorNamespace(None))
+                }
 
             // Need to stay in the same namespace
             } else {
@@ -210,51 +699,153 @@ impl Field for Header {
         Ok(Header {
             version,
             length: length as usize,
-            size: cursor.position() as usize,
+            size: cursor.position(),
             present: kinds,
+            // The TLV fields can't be located after an unsupported field
+            tlv: tlv && !unsupported,
+            skipped,
+            resets,
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut words = vec![0u32];
+        // The index of the first present word of the current namespace
+        let mut start = 0;
+        let mut vendor_namespace = false;
+
+        // A namespace may be started again after the last field
+        for position in 0..=self.present.len() {
+            for _ in self.resets.iter().filter(|&&reset| reset == position) {
+                // Need to move to the next radiotap namespace
+                *words.last_mut().unwrap() |= 1 << 29;
+                words.push(0);
+                start = words.len() - 1;
+                vendor_namespace = false;
+            }
+            let kind = match self.present.get(position) {
+                Some(kind) => kind,
+                None => break,
+            };
+            let bit = match *kind {
+                Kind::VendorNamespace(_) => {
+                    // Need to move to vendor namespace
+                    *words.last_mut().unwrap() |= 1 << 30;
+                    words.push(0);
+                    start = words.len() - 1;
+                    vendor_namespace = true;
+                    continue;
+                }
+                Kind::VendorField(bit) => bit,
+                Kind::USIG | Kind::EHT | Kind::TLV(_) => continue,
+                kind => {
+                    if vendor_namespace {
+                        // Need to move to radiotap namespace
+                        *words.last_mut().unwrap() |= 1 << 29;
+                        words.push(0);
+                        start = words.len() - 1;
+                        vendor_namespace = false;
+                    }
+                    kind.bit()
+                }
+            } as usize;
+            let index = start + bit / 32;
+            if words.len() <= index {
+                words.resize(index + 1, 0);
+            }
+            words[index] |= 1 << (bit % 32);
+        }
+
+        if self.tlv {
+            if vendor_namespace {
+                // Need to move to radiotap namespace
+                *words.last_mut().unwrap() |= 1 << 29;
+                words.push(0);
+            }
+            *words.last_mut().unwrap() |= 1 << 28;
+        }
+
+        let last = words.len() - 1;
+        for word in &mut words[..last] {
+            // More present words exist
+            *word |= 1 << 31;
+        }
+
+        let mut output = vec![self.version, 0];
+        output.extend_from_slice(&(self.length as u16).to_le_bytes());
+        for word in words {
+            output.extend_from_slice(&word.to_le_bytes());
+        }
+        output
+    }
 }
 
+/// The header of a vendor namespace, identifying the vendor and how many bytes
+/// of vendor fields follow.
+///
+/// Parsing fails with an `IncompleteError` if the input is shorter than the 6
+/// bytes of the header.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VendorNamespace {
-    pub oui: OUI,
+    pub oui: Oui,
     pub sub_namespace: u8,
     pub skip_length: u16,
 }
 
 impl Field for VendorNamespace {
     fn from_bytes(input: &[u8]) -> Result<VendorNamespace> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
         let mut oui = [0; 3];
         cursor.read_exact(&mut oui)?;
         let sub_namespace = cursor.read_u8()?;
-        let skip_length = cursor.read_u16::<LE>()?;
+        let skip_length = cursor.read_u16()?;
         Ok(VendorNamespace {
             oui,
             sub_namespace,
             skip_length,
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut output = self.oui.to_vec();
+        output.push(self.sub_namespace);
+        output.extend_from_slice(&self.skip_length.to_le_bytes());
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(6)
+    }
 }
 
 /// Value in microseconds of the MAC’s 64-bit 802.11 Time Synchronization
 /// Function timer when the first bit of the MPDU arrived at the MAC. For
 /// received frames only.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TSFT {
     pub value: u64,
 }
 
 impl Field for TSFT {
     fn from_bytes(input: &[u8]) -> Result<TSFT> {
-        let value = Cursor::new(input).read_u64::<LE>()?;
+        let value = ByteReader::new(input).read_u64()?;
         Ok(TSFT { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(8)
+    }
 }
 
 /// Properties of transmitted and received frames.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Flags {
     /// The frame was sent/received during CFP.
     pub cfp: bool,
@@ -277,7 +868,7 @@ pub struct Flags {
 
 impl Field for Flags {
     fn from_bytes(input: &[u8]) -> Result<Flags> {
-        let flags = Cursor::new(input).read_u8()?;
+        let flags = ByteReader::new(input).read_u8()?;
         Ok(Flags {
             cfp: flags.is_flag_set(0x01),
             preamble: flags.is_flag_set(0x02),
@@ -289,26 +880,72 @@ impl Field for Flags {
             sgi: flags.is_flag_set(0x80),
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let flags = [
+            (self.cfp, 0x01),
+            (self.preamble, 0x02),
+            (self.wep, 0x04),
+            (self.fragmentation, 0x08),
+            (self.fcs, 0x10),
+            (self.data_pad, 0x20),
+            (self.bad_fcs, 0x40),
+            (self.sgi, 0x80),
+        ];
+        vec![flags_value(&flags)]
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// The legacy data rate in Mbps. Usually only one of the
 /// [Rate](struct.Rate.html), [MCS](struct.MCS.html), and [VHT](struct.VHT.html)
 /// fields is present.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rate {
     pub value: f32,
 }
 
 impl Field for Rate {
     fn from_bytes(input: &[u8]) -> Result<Rate> {
-        let value = f32::from(Cursor::new(input).read_i8()?) / 2.0;
+        let value = f32::from(ByteReader::new(input).read_i8()?) / 2.0;
         Ok(Rate { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.raw()]
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl Rate {
+    /// Returns the rate in its raw units of 500 Kbps, as it is encoded.
+    pub fn raw(&self) -> u8 {
+        (self.value * 2.0) as i8 as u8
+    }
+
+    /// Returns whether the rates are within the given tolerance in Mbps.
+    pub fn approx_eq(&self, other: &Rate, epsilon: f32) -> bool {
+        datarate_approx_eq(Some(self.value), Some(other.value), epsilon)
+    }
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1} Mbps", self.value)
+    }
 }
 
 /// The transmitted or received frequency in MHz, including flags describing the
 /// channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Channel {
     /// The frequency in MHz.
     pub freq: u16,
@@ -318,9 +955,9 @@ pub struct Channel {
 
 impl Field for Channel {
     fn from_bytes(input: &[u8]) -> Result<Channel> {
-        let mut cursor = Cursor::new(input);
-        let freq = cursor.read_u16::<LE>()?;
-        let flags = cursor.read_u16::<LE>()?;
+        let mut cursor = ByteReader::new(input);
+        let freq = cursor.read_u16()?;
+        let flags = cursor.read_u16()?;
         let flags = ChannelFlags {
             turbo: flags.is_flag_set(0x0010),
             cck: flags.is_flag_set(0x0020),
@@ -333,10 +970,96 @@ impl Field for Channel {
         };
         Ok(Channel { freq, flags })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let flags = [
+            (self.flags.turbo, 0x0010),
+            (self.flags.cck, 0x0020),
+            (self.flags.ofdm, 0x0040),
+            (self.flags.ghz2, 0x0080),
+            (self.flags.ghz5, 0x0100),
+            (self.flags.passive, 0x0200),
+            (self.flags.dynamic, 0x0400),
+            (self.flags.gfsk, 0x0800),
+        ];
+        let mut output = Vec::with_capacity(4);
+        output.extend_from_slice(&self.freq.to_le_bytes());
+        output.extend_from_slice(&flags_value::<u16>(&flags).to_le_bytes());
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(4)
+    }
+}
+
+impl Channel {
+    /// Returns the band of the frequency, or `None` if the frequency is not in
+    /// the 2.4, 5, or 6 GHz bands.
+    ///
+    /// The band is inferred from the frequency, the channel flags have no
+    /// 6 GHz flag and drivers often set the 5 GHz flag for 6 GHz channels.
+    pub fn band(&self) -> Option<Band> {
+        freq_band(self.freq)
+    }
+
+    /// Returns the IEEE 802.11 channel number of the frequency, or `None` if
+    /// the frequency is not a channel in the 2.4, 5, or 6 GHz bands or doesn't
+    /// match the band in the channel flags. The band is given by
+    /// [band](#method.band).
+    pub fn channel_number(&self) -> Option<u16> {
+        let band = self.band()?;
+
+        if (self.flags.ghz2 && band != Band::GHz2) || (self.flags.ghz5 && band == Band::GHz2) {
+            return None;
+        }
+
+        freq_to_channel(self.freq, band)
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} MHz", self.freq)?;
+
+        let band = if self.flags.ghz2 {
+            Some("2.4 GHz")
+        } else if self.flags.ghz5 {
+            Some("5 GHz")
+        } else {
+            None
+        };
+        let modulation = if self.flags.dynamic {
+            Some("CCK/OFDM")
+        } else if self.flags.ofdm {
+            Some("OFDM")
+        } else if self.flags.cck {
+            Some("CCK")
+        } else if self.flags.gfsk {
+            Some("GFSK")
+        } else {
+            None
+        };
+
+        let mut details = Vec::new();
+        if let Some(number) = self.channel_number() {
+            details.push(format!("ch {}", number));
+        }
+        let kind: Vec<&str> = band.into_iter().chain(modulation).collect();
+        if !kind.is_empty() {
+            details.push(kind.join(" "));
+        }
+
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
 }
 
 /// The hop set and pattern for frequency-hopping radios.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FHSS {
     pub hopset: u8,
     pub pattern: u8,
@@ -344,156 +1067,282 @@ pub struct FHSS {
 
 impl Field for FHSS {
     fn from_bytes(input: &[u8]) -> Result<FHSS> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
         let hopset = cursor.read_u8()?;
         let pattern = cursor.read_u8()?;
         Ok(FHSS { hopset, pattern })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.hopset, self.pattern]
+    }
+
+    fn size() -> Option<usize> {
+        Some(2)
+    }
 }
 
 /// RF signal power at the antenna in dBm. Indicates the RF signal power at the
 /// antenna, in decibels difference from 1mW.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AntennaSignal {
     pub value: i8,
 }
 
 impl Field for AntennaSignal {
     fn from_bytes(input: &[u8]) -> Result<AntennaSignal> {
-        let value = Cursor::new(input).read_i8()?;
+        let value = ByteReader::new(input).read_i8()?;
         Ok(AntennaSignal { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl AntennaSignal {
+    /// Returns the signal as a quality from 0 to 100 percent, linear from 0%
+    /// at -100 dBm to 100% at -50 dBm and clamped outside of that range.
+    pub fn quality_percent(&self) -> u8 {
+        let value = i16::from(self.value).clamp(-100, -50);
+        ((value + 100) * 2) as u8
+    }
+}
+
+impl fmt::Display for AntennaSignal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} dBm", self.value)
+    }
 }
 
 /// RF signal power at the antenna in dB. Indicates the RF signal power at the
 /// antenna, in decibels difference from an arbitrary, fixed reference.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AntennaSignalDb {
     pub value: u8,
 }
 
 impl Field for AntennaSignalDb {
     fn from_bytes(input: &[u8]) -> Result<AntennaSignalDb> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = ByteReader::new(input).read_u8()?;
         Ok(AntennaSignalDb { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// RF noise power at the antenna in dBm. Indicates the RF signal noise at the
 /// antenna, in decibels  difference from 1mW.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AntennaNoise {
     pub value: i8,
 }
 
 impl Field for AntennaNoise {
     fn from_bytes(input: &[u8]) -> Result<AntennaNoise> {
-        let value = Cursor::new(input).read_i8()?;
+        let value = ByteReader::new(input).read_i8()?;
         Ok(AntennaNoise { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl fmt::Display for AntennaNoise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} dBm", self.value)
+    }
 }
 
 /// RF noise power at the antenna in dB. Indicates the RF signal noise at the
 /// antenna, in decibels difference from an arbitrary, fixed reference.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AntennaNoiseDb {
     pub value: u8,
 }
 
 impl Field for AntennaNoiseDb {
     fn from_bytes(input: &[u8]) -> Result<AntennaNoiseDb> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = ByteReader::new(input).read_u8()?;
         Ok(AntennaNoiseDb { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// Quality of Barker code lock, unitless. Monotonically nondecreasing with
 /// "better" lock strength. Called "Signal Quality" in datasheets.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LockQuality {
     pub value: u16,
 }
 
 impl Field for LockQuality {
     fn from_bytes(input: &[u8]) -> Result<LockQuality> {
-        let value = Cursor::new(input).read_u16::<LE>()?;
+        let value = ByteReader::new(input).read_u16()?;
         Ok(LockQuality { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(2)
+    }
 }
 
 /// Transmit power expressed as unitless distance from max power. 0 is max
 /// power. Monotonically nondecreasing with lower power levels.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TxAttenuation {
     pub value: u16,
 }
 
 impl Field for TxAttenuation {
     fn from_bytes(input: &[u8]) -> Result<TxAttenuation> {
-        let value = Cursor::new(input).read_u16::<LE>()?;
+        let value = ByteReader::new(input).read_u16()?;
         Ok(TxAttenuation { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(2)
+    }
 }
 
 /// Transmit power in dB. 0 is max power. Monotonically nondecreasing with lower
 /// power levels.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TxAttenuationDb {
     pub value: u16,
 }
 
 impl Field for TxAttenuationDb {
     fn from_bytes(input: &[u8]) -> Result<TxAttenuationDb> {
-        let value = Cursor::new(input).read_u16::<LE>()?;
+        let value = ByteReader::new(input).read_u16()?;
         Ok(TxAttenuationDb { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(2)
+    }
 }
 
 /// Transmit power in dBm. This is the absolute power level measured at the
 /// antenna port.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TxPower {
     pub value: i8,
 }
 
 impl Field for TxPower {
     fn from_bytes(input: &[u8]) -> Result<TxPower> {
-        let value = Cursor::new(input).read_i8()?;
+        let value = ByteReader::new(input).read_i8()?;
         Ok(TxPower { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// Indication of the transmit/receive antenna for this frame. The first antenna
 /// is antenna 0.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Antenna {
     pub value: u8,
 }
 
 impl Field for Antenna {
     fn from_bytes(input: &[u8]) -> Result<Antenna> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = ByteReader::new(input).read_u8()?;
         Ok(Antenna { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// Properties of received frames.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RxFlags {
     pub bad_plcp: bool,
 }
 
 impl Field for RxFlags {
     fn from_bytes(input: &[u8]) -> Result<RxFlags> {
-        let flags = Cursor::new(input).read_u16::<LE>()?;
+        let flags = ByteReader::new(input).read_u16()?;
         Ok(RxFlags {
             bad_plcp: flags.is_flag_set(0x0002),
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let flags = [(self.bad_plcp, 0x0002)];
+        flags_value::<u16>(&flags).to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(2)
+    }
 }
 
 /// Properties of transmitted frames.
+///
+/// The TX flags have no bandwidth or MCS sub-fields. The rate, MCS and
+/// bandwidth of an injected frame are given by the [Rate](struct.Rate.html),
+/// [MCS](struct.MCS.html) and [VHT](struct.VHT.html) fields, with their known
+/// bits set.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TxFlags {
     /// Transmission failed due to excessive retries.
     pub fail: bool,
@@ -507,49 +1356,88 @@ pub struct TxFlags {
     /// Transmission includes a pre-configured sequence number that should not
     /// be changed by the driver's TX handlers.
     pub no_seq: bool,
+    /// Transmission should not be reordered relative to other frames that
+    /// have this flag set.
+    pub order: bool,
 }
 
 impl Field for TxFlags {
     fn from_bytes(input: &[u8]) -> Result<TxFlags> {
-        let flags = Cursor::new(input).read_u8()?;
+        let flags = ByteReader::new(input).read_u16()?;
         Ok(TxFlags {
             fail: flags.is_flag_set(0x0001),
             cts: flags.is_flag_set(0x0002),
             rts: flags.is_flag_set(0x0004),
             no_ack: flags.is_flag_set(0x0008),
             no_seq: flags.is_flag_set(0x0010),
+            order: flags.is_flag_set(0x0020),
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let flags = [
+            (self.fail, 0x0001),
+            (self.cts, 0x0002),
+            (self.rts, 0x0004),
+            (self.no_ack, 0x0008),
+            (self.no_seq, 0x0010),
+            (self.order, 0x0020),
+        ];
+        flags_value::<u16>(&flags).to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(2)
+    }
 }
 
 /// Number of RTS retries a transmitted frame used.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RTSRetries {
     pub value: u8,
 }
 
 impl Field for RTSRetries {
     fn from_bytes(input: &[u8]) -> Result<RTSRetries> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = ByteReader::new(input).read_u8()?;
         Ok(RTSRetries { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// Number of data retries a transmitted frame used.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataRetries {
     pub value: u8,
 }
 
 impl Field for DataRetries {
     fn from_bytes(input: &[u8]) -> Result<DataRetries> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = ByteReader::new(input).read_u8()?;
         Ok(DataRetries { value })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_le_bytes().to_vec()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
 }
 
 /// Extended channel information.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XChannel {
     /// The channel flags.
     pub flags: XChannelFlags,
@@ -563,9 +1451,9 @@ pub struct XChannel {
 
 impl Field for XChannel {
     fn from_bytes(input: &[u8]) -> Result<XChannel> {
-        let mut cursor = Cursor::new(input);
-        let flags = cursor.read_u32::<LE>()?;
-        let freq = cursor.read_u16::<LE>()?;
+        let mut cursor = ByteReader::new(input);
+        let flags = cursor.read_u32()?;
+        let freq = cursor.read_u16()?;
         let channel = cursor.read_u8()?;
         let max_power = cursor.read_u8()?;
         Ok(XChannel {
@@ -591,12 +1479,149 @@ impl Field for XChannel {
             max_power,
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let flags = [
+            (self.flags.turbo, 0x0000_0010),
+            (self.flags.cck, 0x0000_0020),
+            (self.flags.ofdm, 0x0000_0040),
+            (self.flags.ghz2, 0x0000_0080),
+            (self.flags.ghz5, 0x0000_0100),
+            (self.flags.passive, 0x0000_0200),
+            (self.flags.dynamic, 0x0000_0400),
+            (self.flags.gfsk, 0x0000_0800),
+            (self.flags.gsm, 0x0000_1000),
+            (self.flags.sturbo, 0x0000_2000),
+            (self.flags.half, 0x0000_4000),
+            (self.flags.quarter, 0x0000_8000),
+            (self.flags.ht20, 0x0001_0000),
+            (self.flags.ht40u, 0x0002_0000),
+            (self.flags.ht40d, 0x0004_0000),
+        ];
+        let mut output = Vec::with_capacity(8);
+        output.extend_from_slice(&flags_value::<u32>(&flags).to_le_bytes());
+        output.extend_from_slice(&self.freq.to_le_bytes());
+        output.push(self.channel);
+        output.push(self.max_power);
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(8)
+    }
+}
+
+impl XChannel {
+    /// Returns the channel number of the center of the operating channel. For
+    /// an HT40 channel this is two channels above or below the primary channel
+    /// depending on the sideband of the secondary channel.
+    pub fn operating_channel(&self) -> u8 {
+        if self.flags.ht40u {
+            self.channel.wrapping_add(2)
+        } else if self.flags.ht40d {
+            self.channel.wrapping_sub(2)
+        } else {
+            self.channel
+        }
+    }
+
+    /// Returns the center frequency in MHz of the operating channel. For an
+    /// HT40 channel this is 10 MHz above or below the primary channel depending
+    /// on the sideband of the secondary channel.
+    pub fn center_freq(&self) -> u16 {
+        if self.flags.ht40u {
+            self.freq.wrapping_add(10)
+        } else if self.flags.ht40d {
+            self.freq.wrapping_sub(10)
+        } else {
+            self.freq
+        }
+    }
+}
+
+/// The channel from either the [Channel](struct.Channel.html) or
+/// [XChannel](struct.XChannel.html) field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelInfo {
+    /// The frequency in MHz.
+    pub freq: u16,
+    /// The IEEE 802.11 channel number.
+    pub channel: Option<u16>,
+    /// The band of the frequency.
+    pub band: Option<Band>,
+    /// The channel width in MHz, only given by the XChannel flags.
+    pub width: Option<u8>,
+}
+
+impl From<Channel> for ChannelInfo {
+    fn from(channel: Channel) -> ChannelInfo {
+        ChannelInfo {
+            freq: channel.freq,
+            channel: channel.channel_number(),
+            band: freq_band(channel.freq),
+            width: None,
+        }
+    }
+}
+
+impl From<XChannel> for ChannelInfo {
+    fn from(xchannel: XChannel) -> ChannelInfo {
+        let flags = xchannel.flags;
+        let channel = match xchannel.channel {
+            0 => Channel {
+                freq: xchannel.freq,
+                flags: ChannelFlags {
+                    turbo: flags.turbo,
+                    cck: flags.cck,
+                    ofdm: flags.ofdm,
+                    ghz2: flags.ghz2,
+                    ghz5: flags.ghz5,
+                    passive: flags.passive,
+                    dynamic: flags.dynamic,
+                    gfsk: flags.gfsk,
+                },
+            }
+            .channel_number(),
+            channel => Some(u16::from(channel)),
+        };
+        let width = if flags.quarter {
+            Some(5)
+        } else if flags.half {
+            Some(10)
+        } else if flags.ht40u || flags.ht40d {
+            Some(40)
+        } else if flags.ht20 {
+            Some(20)
+        } else {
+            None
+        };
+
+        ChannelInfo {
+            freq: xchannel.freq,
+            channel,
+            band: freq_band(xchannel.freq),
+            width,
+        }
+    }
+}
+
+/// Returns the band of a frequency in MHz. The band flags can't be used as 6
+/// GHz channels are usually flagged as 5 GHz.
+fn freq_band(freq: u16) -> Option<Band> {
+    match freq {
+        2412..=2484 => Some(Band::GHz2),
+        5935 | 5955..=7115 => Some(Band::GHz6),
+        5005..=5945 => Some(Band::GHz5),
+        _ => None,
+    }
 }
 
 /// The IEEE 802.11n data rate index. Usually only one of the
 /// [Rate](struct.Rate.html), [MCS](struct.MCS.html), and [VHT] fields is
 /// present.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MCS {
     /// The bandwidth.
     pub bw: Option<Bandwidth>,
@@ -618,7 +1643,7 @@ pub struct MCS {
 
 impl Field for MCS {
     fn from_bytes(input: &[u8]) -> Result<MCS> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
         let mut mcs = MCS {
             ..Default::default()
         };
@@ -665,40 +1690,148 @@ impl Field for MCS {
 
         if known.is_flag_set(0x40) {
             // Yes this is stored weirdly
-            mcs.ness = Some(known & 0x80 >> 6 | flags & 0x80 >> 7)
+            mcs.ness = Some((known & 0x80) >> 6 | (flags & 0x80) >> 7)
         }
 
-        if mcs.bw.is_some() && mcs.gi.is_some() {
-            mcs.datarate = Some(ht_rate(index, mcs.bw.unwrap(), mcs.gi.unwrap())?);
+        if let (Some(bw), Some(gi)) = (mcs.bw, mcs.gi) {
+            mcs.datarate = Some(ht_rate(index, bw, gi)?);
         }
 
         Ok(mcs)
     }
-}
 
-/// The presence of this field indicates that the frame was received as part of
-/// an a-MPDU.
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
-pub struct AMPDUStatus {
-    /// The A-MPDU reference number.
-    pub reference: u32,
-    /// Whether this is a 0-length subframe.
-    pub zero_length: Option<bool>,
-    /// Whether this is the last subframe of this A-MPDU.
-    pub last: Option<bool>,
-    /// The A-MPDU subframe delimiter CRC.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut known = 0;
+        let mut flags = 0;
+        let mut index = 0;
+
+        if let Some(value) = self.bw.and_then(Bandwidth::value) {
+            known |= 0x01;
+            flags |= value & 0x03;
+        }
+
+        if let Some(value) = self.index {
+            known |= 0x02;
+            index = value;
+        }
+
+        if let Some(gi) = self.gi {
+            known |= 0x04;
+            if gi == GuardInterval::Short {
+                flags |= 0x04;
+            }
+        }
+
+        if let Some(format) = self.format {
+            known |= 0x08;
+            if format == HTFormat::Greenfield {
+                flags |= 0x08;
+            }
+        }
+
+        if let Some(fec) = self.fec {
+            known |= 0x10;
+            if fec == FEC::LDPC {
+                flags |= 0x10;
+            }
+        }
+
+        if let Some(stbc) = self.stbc {
+            known |= 0x20;
+            flags |= (stbc & 0x03) << 5;
+        }
+
+        if let Some(ness) = self.ness {
+            // The high bit of NESS is stored in the known field
+            known |= 0x40 | (ness & 0x02) << 6;
+            flags |= (ness & 0x01) << 7;
+        }
+
+        vec![known, flags, index]
+    }
+
+    fn size() -> Option<usize> {
+        Some(3)
+    }
+}
+
+impl MCS {
+    /// Returns the datarate in Mbps, assuming a long guard interval if the
+    /// guard interval is unknown. Returns `None` if the index or bandwidth is
+    /// unknown.
+    ///
+    /// Many drivers leave the guard interval unknown when they only use the
+    /// long guard interval, the `datarate` is only known when it is given.
+    pub fn datarate_assuming_lgi(&self) -> Option<f32> {
+        if self.datarate.is_some() {
+            return self.datarate;
+        }
+
+        let gi = self.gi.unwrap_or(GuardInterval::Long);
+        ht_rate(self.index?, self.bw?, gi).ok()
+    }
+
+    /// Returns whether the fields are equal, with datarates compared within
+    /// the given tolerance in Mbps.
+    pub fn approx_eq(&self, other: &MCS, epsilon: f32) -> bool {
+        MCS {
+            datarate: None,
+            ..*self
+        } == MCS {
+            datarate: None,
+            ..*other
+        } && datarate_approx_eq(self.datarate, other.datarate, epsilon)
+    }
+}
+
+impl fmt::Display for MCS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(index) = self.index {
+            parts.push(format!("MCS {}", index));
+        }
+        if let Some(bw) = self.bw {
+            parts.push(format!("{}", bw));
+        }
+        if let Some(gi) = self.gi {
+            parts.push(format!("{}", gi));
+        }
+        if let Some(datarate) = self.datarate {
+            parts.push(format!("{:.1} Mbps", datarate));
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// The presence of this field indicates that the frame was received as part of
+/// an a-MPDU.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AMPDUStatus {
+    /// The A-MPDU reference number.
+    pub reference: u32,
+    /// Whether this is a 0-length subframe.
+    pub zero_length: Option<bool>,
+    /// Whether this is the last subframe of this A-MPDU.
+    pub last: Option<bool>,
+    /// The A-MPDU subframe delimiter CRC.
     pub delimiter_crc: Option<u8>,
+    /// Whether the delimiter CRC had an error, reported even when the
+    /// delimiter CRC is not known.
+    pub delimiter_crc_error: Option<bool>,
+    /// Whether the subframe has the EOF bit set.
+    pub eof: Option<bool>,
 }
 
 impl Field for AMPDUStatus {
     fn from_bytes(input: &[u8]) -> Result<AMPDUStatus> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
         let mut ampdu = AMPDUStatus {
             ..Default::default()
         };
 
-        ampdu.reference = cursor.read_u32::<LE>()?;
-        let flags = cursor.read_u16::<LE>()?;
+        ampdu.reference = cursor.read_u32()?;
+        let flags = cursor.read_u16()?;
         let delim_crc = cursor.read_u8()?;
 
         if flags.is_flag_set(0x0001) {
@@ -709,18 +1842,71 @@ impl Field for AMPDUStatus {
             ampdu.last = Some(flags.is_flag_set(0x0008));
         }
 
-        if !flags.is_flag_set(0x0010) && flags.is_flag_set(0x0020) {
+        ampdu.delimiter_crc_error = Some(flags.is_flag_set(0x0010));
+
+        if flags.is_flag_set(0x0020) {
             ampdu.delimiter_crc = Some(delim_crc);
         }
 
+        if flags.is_flag_set(0x0080) {
+            ampdu.eof = Some(flags.is_flag_set(0x0040));
+        }
+
         Ok(ampdu)
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut flags: u16 = 0;
+        let mut delim_crc = 0;
+
+        if let Some(zero_length) = self.zero_length {
+            flags |= 0x0001;
+            if zero_length {
+                flags |= 0x0002;
+            }
+        }
+
+        if let Some(last) = self.last {
+            flags |= 0x0004;
+            if last {
+                flags |= 0x0008;
+            }
+        }
+
+        if self.delimiter_crc_error == Some(true) {
+            flags |= 0x0010;
+        }
+
+        if let Some(value) = self.delimiter_crc {
+            flags |= 0x0020;
+            delim_crc = value;
+        }
+
+        if let Some(eof) = self.eof {
+            flags |= 0x0080;
+            if eof {
+                flags |= 0x0040;
+            }
+        }
+
+        let mut output = Vec::with_capacity(8);
+        output.extend_from_slice(&self.reference.to_le_bytes());
+        output.extend_from_slice(&flags.to_le_bytes());
+        output.push(delim_crc);
+        output.push(0); // Reserved
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(8)
+    }
 }
 
 /// The IEEE 802.11ac data rate index. Usually only one of the
 /// [Rate](struct.Rate.html), [MCS](struct.MCS.html), and [VHT](struct.VHT.html)
 /// fields is present.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VHT {
     /// Whether all spatial streams of all users have STBC.
     pub stbc: Option<bool>,
@@ -748,19 +1934,19 @@ pub struct VHT {
 
 impl Field for VHT {
     fn from_bytes(input: &[u8]) -> Result<VHT> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
         let mut vht = VHT {
             ..Default::default()
         };
 
-        let known = cursor.read_u16::<LE>()?;
+        let known = cursor.read_u16()?;
         let flags = cursor.read_u8()?;
         let bandwidth = cursor.read_u8()?;
         let mut mcs_nss = [0; 4];
         cursor.read_exact(&mut mcs_nss)?;
         let coding = cursor.read_u8()?;
         let group_id = cursor.read_u8()?;
-        let partial_aid = cursor.read_u16::<LE>()?;
+        let partial_aid = cursor.read_u16()?;
 
         if known.is_flag_set(0x0001) {
             vht.stbc = Some(flags.is_flag_set(0x01));
@@ -810,18 +1996,18 @@ impl Field for VHT {
             }
 
             let index = (user & 0xf0) >> 4;
-            let nsts = nss << (flags & 0x01);
+            // STBC applies to all users, doubling the space-time streams
+            let nsts = if vht.stbc == Some(true) { nss * 2 } else { nss };
             let id = i as u8;
 
-            let datarate = if vht.bw.is_some() && vht.gi.is_some() {
-                Some(vht_rate(index, vht.bw.unwrap(), vht.gi.unwrap(), nss)?)
-            } else {
-                None
+            let datarate = match (vht.bw, vht.gi) {
+                (Some(bw), Some(gi)) => Some(vht_rate(index, bw, gi, nss)?),
+                _ => None,
             };
 
             vht.users[id as usize] = Some(VHTUser {
                 index,
-                fec: match (coding & 2 ^ id) >> id {
+                fec: match (coding >> id) & 1 {
                     1 => FEC::LDPC,
                     _ => FEC::BCC,
                 },
@@ -833,10 +2019,113 @@ impl Field for VHT {
 
         Ok(vht)
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut known: u16 = 0;
+        let mut flags = 0;
+        let mut bandwidth = 0;
+        let mut mcs_nss = [0; 4];
+        let mut coding = 0;
+
+        let bools = [
+            (self.stbc, 0x01),
+            (self.txop_ps, 0x02),
+            (self.gi.map(|gi| gi == GuardInterval::Short), 0x04),
+            (self.sgi_nsym_da, 0x08),
+            (self.ldpc_extra, 0x10),
+            (self.beamformed, 0x20),
+        ];
+        for &(value, flag) in bools.iter() {
+            if let Some(value) = value {
+                known |= u16::from(flag);
+                if value {
+                    flags |= flag;
+                }
+            }
+        }
+
+        if let Some(value) = self.bw.and_then(Bandwidth::value) {
+            known |= 0x0040;
+            bandwidth = value;
+        }
+
+        if self.group_id.is_some() {
+            known |= 0x0080;
+        }
+
+        if self.partial_aid.is_some() {
+            known |= 0x0100;
+        }
+
+        for (i, user) in self.users.iter().enumerate() {
+            if let Some(user) = user {
+                mcs_nss[i] = user.index << 4 | (user.nss & 0x0f);
+                if user.fec == FEC::LDPC {
+                    coding |= 1 << i;
+                }
+            }
+        }
+
+        let mut output = Vec::with_capacity(12);
+        output.extend_from_slice(&known.to_le_bytes());
+        output.push(flags);
+        output.push(bandwidth);
+        output.extend_from_slice(&mcs_nss);
+        output.push(coding);
+        output.push(self.group_id.unwrap_or(0));
+        output.extend_from_slice(&self.partial_aid.unwrap_or(0).to_le_bytes());
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(12)
+    }
+}
+
+impl VHT {
+    /// Returns whether the fields are equal, with the datarates of the users
+    /// compared within the given tolerance in Mbps.
+    pub fn approx_eq(&self, other: &VHT, epsilon: f32) -> bool {
+        let users = self
+            .users
+            .iter()
+            .zip(other.users.iter())
+            .all(|users| match users {
+                (Some(a), Some(b)) => a.approx_eq(b, epsilon),
+                (None, None) => true,
+                _ => false,
+            });
+
+        users
+            && VHT {
+                users: [None; 4],
+                ..*self
+            } == VHT {
+                users: [None; 4],
+                ..*other
+            }
+    }
+}
+
+impl fmt::Display for VHT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(bw) = self.bw {
+            parts.push(format!("{}", bw));
+        }
+        if let Some(gi) = self.gi {
+            parts.push(format!("{}", gi));
+        }
+        for user in self.users.iter().flatten() {
+            parts.push(format!("{}", user));
+        }
+        f.write_str(&parts.join(", "))
+    }
 }
 
 /// The time the frame was transmitted or received.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Timestamp {
     /// The actual timestamp.
     pub timestamp: u64,
@@ -846,28 +2135,1662 @@ pub struct Timestamp {
     pub position: SamplingPosition,
     /// The accuracy of the timestamp.
     pub accuracy: Option<u16>,
+    /// Whether the timestamp is a 32-bit counter, only the low 32 bits are
+    /// valid.
+    pub counter_32bit: bool,
+}
+
+impl Timestamp {
+    /// Returns the timestamp converted to microseconds, saturating if it
+    /// overflows.
+    pub fn micros(&self) -> u64 {
+        match self.unit {
+            TimeUnit::Milliseconds => self.timestamp.saturating_mul(1000),
+            TimeUnit::Microseconds => self.timestamp,
+            TimeUnit::Nanoseconds => self.timestamp / 1000,
+        }
+    }
 }
 
 impl Field for Timestamp {
     fn from_bytes(input: &[u8]) -> Result<Timestamp> {
-        let mut cursor = Cursor::new(input);
+        let mut cursor = ByteReader::new(input);
 
-        let timestamp = cursor.read_u64::<LE>()?;
-        let mut accuracy = Some(cursor.read_u16::<LE>()?);
+        let timestamp = cursor.read_u64()?;
+        let accuracy = cursor.read_u16()?;
+        // The unit is in the low nibble, the sampling position in the high
         let unit_position = cursor.read_u8()?;
         let unit = TimeUnit::new(unit_position & 0x0f)?;
-        let position = SamplingPosition::from(unit_position & 0xf0 >> 4)?;
+        let position = SamplingPosition::from((unit_position & 0xf0) >> 4)?;
         let flags = cursor.read_u8()?;
 
-        if !flags.is_flag_set(0x02) {
-            accuracy = None;
-        }
-
         Ok(Timestamp {
             timestamp,
             unit,
             position,
-            accuracy,
+            accuracy: if flags.is_flag_set(0x02) {
+                Some(accuracy)
+            } else {
+                None
+            },
+            counter_32bit: flags.is_flag_set(0x01),
         })
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut output = Vec::with_capacity(12);
+        output.extend_from_slice(&self.timestamp.to_le_bytes());
+        output.extend_from_slice(&self.accuracy.unwrap_or(0).to_le_bytes());
+        output.push(self.unit.value() | self.position.value() << 4);
+        let flags = [(self.counter_32bit, 0x01), (self.accuracy.is_some(), 0x02)];
+        output.push(flags_value(&flags));
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(12)
+    }
+}
+
+/// The IEEE 802.11ax HE information. Unknown sub-fields are `None`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HE {
+    /// The HE PPDU format.
+    pub format: HEFormat,
+    /// The BSS color.
+    pub bss_color: Option<u8>,
+    /// Whether the pre-HE modulated fields are spatially mapped differently.
+    pub beam_change: Option<bool>,
+    /// Whether the PPDU is sent to an AP (uplink).
+    pub ul_dl: Option<bool>,
+    /// The HE-MCS index.
+    pub index: Option<u8>,
+    /// Whether dual carrier modulation is used.
+    pub dcm: Option<bool>,
+    /// The FEC type.
+    pub fec: Option<FEC>,
+    /// Whether the LDPC encoding process resulted in an extra OFDM symbol
+    /// segment.
+    pub ldpc_extra: Option<bool>,
+    /// Whether STBC is used.
+    pub stbc: Option<bool>,
+    /// The bandwidth or resource unit allocation.
+    pub bw: Option<HEBandwidth>,
+    /// The guard interval.
+    pub gi: Option<HEGuardInterval>,
+    /// The HE-LTF symbol size.
+    pub ltf_size: Option<LTFSize>,
+    /// The number of HE-LTF symbols.
+    pub ltf_symbols: Option<u8>,
+    /// Whether transmit beamforming is used.
+    pub txbf: Option<bool>,
+    /// The packet extension disambiguity.
+    pub pe_disambiguity: Option<bool>,
+    /// The TXOP duration.
+    pub txop: Option<u8>,
+    /// Whether doppler is used.
+    pub doppler: Option<bool>,
+    /// Number of space-time streams.
+    pub nsts: Option<u8>,
+}
+
+/// The number of HE-LTF symbols for each raw value.
+const LTF_SYMBOLS: [u8; 5] = [1, 2, 4, 6, 8];
+
+impl Field for HE {
+    fn from_bytes(input: &[u8]) -> Result<HE> {
+        let mut cursor = ByteReader::new(input);
+
+        let data1 = cursor.read_u16()?;
+        let data2 = cursor.read_u16()?;
+        let data3 = cursor.read_u16()?;
+        let _data4 = cursor.read_u16()?;
+        let data5 = cursor.read_u16()?;
+        let data6 = cursor.read_u16()?;
+
+        let mut he = HE {
+            format: HEFormat::new((data1 & 0x0003) as u8)?,
+            bss_color: None,
+            beam_change: None,
+            ul_dl: None,
+            index: None,
+            dcm: None,
+            fec: None,
+            ldpc_extra: None,
+            stbc: None,
+            bw: None,
+            gi: None,
+            ltf_size: None,
+            ltf_symbols: None,
+            txbf: None,
+            pe_disambiguity: None,
+            txop: None,
+            doppler: None,
+            nsts: None,
+        };
+
+        if data1.is_flag_set(0x0004) {
+            he.bss_color = Some((data3 & 0x003f) as u8);
+        }
+
+        if data1.is_flag_set(0x0008) {
+            he.beam_change = Some(data3.is_flag_set(0x0040));
+        }
+
+        if data1.is_flag_set(0x0010) {
+            he.ul_dl = Some(data3.is_flag_set(0x0080));
+        }
+
+        if data1.is_flag_set(0x0020) {
+            he.index = Some(((data3 & 0x0f00) >> 8) as u8);
+        }
+
+        if data1.is_flag_set(0x0040) {
+            he.dcm = Some(data3.is_flag_set(0x1000));
+        }
+
+        if data1.is_flag_set(0x0080) {
+            he.fec = Some(if data3.is_flag_set(0x2000) {
+                FEC::LDPC
+            } else {
+                FEC::BCC
+            });
+        }
+
+        if data1.is_flag_set(0x0100) {
+            he.ldpc_extra = Some(data3.is_flag_set(0x4000));
+        }
+
+        if data1.is_flag_set(0x0200) {
+            he.stbc = Some(data3.is_flag_set(0x8000));
+        }
+
+        if data1.is_flag_set(0x4000) {
+            he.bw = Some(HEBandwidth::new((data5 & 0x000f) as u8)?);
+        }
+
+        if data1.is_flag_set(0x8000) {
+            he.doppler = Some(data6.is_flag_set(0x0010));
+        }
+
+        if data2.is_flag_set(0x0002) {
+            he.gi = Some(HEGuardInterval::new(((data5 & 0x0030) >> 4) as u8)?);
+        }
+
+        if data2.is_flag_set(0x0004) {
+            let symbols = LTF_SYMBOLS.get(((data5 & 0x0700) >> 8) as usize);
+            he.ltf_symbols = Some(*symbols.ok_or_else(Error::invalid_format)?);
+        }
+
+        if data2.is_flag_set(0x0010) {
+            he.txbf = Some(data5.is_flag_set(0x4000));
+        }
+
+        if data2.is_flag_set(0x0020) {
+            he.pe_disambiguity = Some(data5.is_flag_set(0x8000));
+        }
+
+        if data2.is_flag_set(0x0040) {
+            he.txop = Some(((data6 & 0x7f00) >> 8) as u8);
+        }
+
+        // A value of 0 means the LTF size is unknown
+        let ltf_size = ((data5 & 0x00c0) >> 6) as u8;
+        if ltf_size != 0 {
+            he.ltf_size = Some(LTFSize::new(ltf_size)?);
+        }
+
+        // A value of 0 means the number of space-time streams is unknown
+        let nsts = (data6 & 0x000f) as u8;
+        if nsts != 0 {
+            he.nsts = Some(nsts);
+        }
+
+        Ok(he)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data1 = u16::from(self.format.value());
+        let mut data2: u16 = 0;
+        let mut data3: u16 = 0;
+        let mut data5: u16 = 0;
+        let mut data6: u16 = 0;
+
+        if let Some(bss_color) = self.bss_color {
+            data1 |= 0x0004;
+            data3 |= u16::from(bss_color) & 0x003f;
+        }
+
+        if let Some(index) = self.index {
+            data1 |= 0x0020;
+            data3 |= (u16::from(index) & 0x0f) << 8;
+        }
+
+        if let Some(fec) = self.fec {
+            data1 |= 0x0080;
+            if fec == FEC::LDPC {
+                data3 |= 0x2000;
+            }
+        }
+
+        let data3_bools = [
+            (self.beam_change, 0x0008, 0x0040),
+            (self.ul_dl, 0x0010, 0x0080),
+            (self.dcm, 0x0040, 0x1000),
+            (self.ldpc_extra, 0x0100, 0x4000),
+            (self.stbc, 0x0200, 0x8000),
+        ];
+        for &(value, known, flag) in data3_bools.iter() {
+            if let Some(value) = value {
+                data1 |= known;
+                if value {
+                    data3 |= flag;
+                }
+            }
+        }
+
+        if let Some(bw) = self.bw {
+            data1 |= 0x4000;
+            data5 |= u16::from(bw.value());
+        }
+
+        if let Some(doppler) = self.doppler {
+            data1 |= 0x8000;
+            if doppler {
+                data6 |= 0x0010;
+            }
+        }
+
+        if let Some(gi) = self.gi {
+            data2 |= 0x0002;
+            data5 |= u16::from(gi.value()) << 4;
+        }
+
+        if let Some(symbols) = self.ltf_symbols {
+            if let Some(value) = LTF_SYMBOLS.iter().position(|&s| s == symbols) {
+                data2 |= 0x0004;
+                data5 |= (value as u16) << 8;
+            }
+        }
+
+        if let Some(txbf) = self.txbf {
+            data2 |= 0x0010;
+            if txbf {
+                data5 |= 0x4000;
+            }
+        }
+
+        if let Some(pe_disambiguity) = self.pe_disambiguity {
+            data2 |= 0x0020;
+            if pe_disambiguity {
+                data5 |= 0x8000;
+            }
+        }
+
+        if let Some(txop) = self.txop {
+            data2 |= 0x0040;
+            data6 |= (u16::from(txop) & 0x7f) << 8;
+        }
+
+        if let Some(ltf_size) = self.ltf_size {
+            data5 |= u16::from(ltf_size.value()) << 6;
+        }
+
+        if let Some(nsts) = self.nsts {
+            data6 |= u16::from(nsts) & 0x000f;
+        }
+
+        let mut output = Vec::with_capacity(12);
+        for data in [data1, data2, data3, 0, data5, data6].iter() {
+            output.extend_from_slice(&data.to_le_bytes());
+        }
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(12)
+    }
+}
+
+/// The IEEE 802.11ax HE-MU information, from the HE-SIG-A and HE-SIG-B fields
+/// of multi-user frames. Unknown sub-fields are `None`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HEMU {
+    /// The SIG-B MCS index.
+    pub sig_b_index: Option<u8>,
+    /// Whether dual carrier modulation is used for SIG-B.
+    pub sig_b_dcm: Option<bool>,
+    /// Whether SIG-B compression is used.
+    pub sig_b_compression: Option<bool>,
+    /// The number of HE-SIG-B symbols, or the number of MU-MIMO users if SIG-B
+    /// compression is used.
+    pub sig_b_symbols: Option<u8>,
+    /// The bandwidth from the HE-SIG-A field.
+    pub bw: Option<HEBandwidth>,
+    /// The preamble puncturing from the HE-SIG-A bandwidth field.
+    pub preamble_puncturing: Option<u8>,
+    /// Whether the center 26-tone RU of channel 1 is used.
+    pub center_ru_channel1: Option<bool>,
+    /// Whether the center 26-tone RU of channel 2 is used.
+    pub center_ru_channel2: Option<bool>,
+    /// The RU allocations of channel 1.
+    pub ru_channel1: Option<[u8; 4]>,
+    /// The RU allocations of channel 2.
+    pub ru_channel2: Option<[u8; 4]>,
+}
+
+impl Field for HEMU {
+    fn from_bytes(input: &[u8]) -> Result<HEMU> {
+        let mut cursor = ByteReader::new(input);
+        let mut he_mu = HEMU {
+            ..Default::default()
+        };
+
+        let flags1 = cursor.read_u16()?;
+        let flags2 = cursor.read_u16()?;
+        let mut ru_channel1 = [0; 4];
+        cursor.read_exact(&mut ru_channel1)?;
+        let mut ru_channel2 = [0; 4];
+        cursor.read_exact(&mut ru_channel2)?;
+
+        if flags1.is_flag_set(0x0010) {
+            he_mu.sig_b_index = Some((flags1 & 0x000f) as u8);
+        }
+
+        if flags1.is_flag_set(0x0040) {
+            he_mu.sig_b_dcm = Some(flags1.is_flag_set(0x0020));
+        }
+
+        if flags1.is_flag_set(0x0080) {
+            he_mu.center_ru_channel2 = Some(flags2.is_flag_set(0x0800));
+        }
+
+        if flags1.is_flag_set(0x0100) {
+            he_mu.ru_channel1 = Some(ru_channel1);
+        }
+
+        if flags1.is_flag_set(0x0200) {
+            he_mu.ru_channel2 = Some(ru_channel2);
+        }
+
+        if flags1.is_flag_set(0x1000) {
+            he_mu.center_ru_channel1 = Some(flags1.is_flag_set(0x2000));
+        }
+
+        if flags1.is_flag_set(0x4000) {
+            he_mu.sig_b_compression = Some(flags2.is_flag_set(0x0008));
+        }
+
+        if flags1.is_flag_set(0x8000) {
+            he_mu.sig_b_symbols = Some(((flags2 & 0x00f0) >> 4) as u8 + 1);
+        }
+
+        if flags2.is_flag_set(0x0004) {
+            he_mu.bw = Some(HEBandwidth::new((flags2 & 0x0003) as u8)?);
+        }
+
+        if flags2.is_flag_set(0x0400) {
+            he_mu.preamble_puncturing = Some(((flags2 & 0x0300) >> 8) as u8);
+        }
+
+        Ok(he_mu)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut flags1: u16 = 0;
+        let mut flags2: u16 = 0;
+
+        if let Some(index) = self.sig_b_index {
+            flags1 |= 0x0010 | (u16::from(index) & 0x000f);
+        }
+
+        if let Some(dcm) = self.sig_b_dcm {
+            flags1 |= 0x0040;
+            if dcm {
+                flags1 |= 0x0020;
+            }
+        }
+
+        if let Some(center) = self.center_ru_channel2 {
+            flags1 |= 0x0080;
+            if center {
+                flags2 |= 0x0800;
+            }
+        }
+
+        if self.ru_channel1.is_some() {
+            flags1 |= 0x0100;
+        }
+
+        if self.ru_channel2.is_some() {
+            flags1 |= 0x0200;
+        }
+
+        if let Some(center) = self.center_ru_channel1 {
+            flags1 |= 0x1000;
+            if center {
+                flags1 |= 0x2000;
+            }
+        }
+
+        if let Some(compression) = self.sig_b_compression {
+            flags1 |= 0x4000;
+            if compression {
+                flags2 |= 0x0008;
+            }
+        }
+
+        if let Some(symbols) = self.sig_b_symbols {
+            flags1 |= 0x8000;
+            flags2 |= (u16::from(symbols.saturating_sub(1)) & 0x000f) << 4;
+        }
+
+        if let Some(bw) = self.bw {
+            flags2 |= 0x0004 | (u16::from(bw.value()) & 0x0003);
+        }
+
+        if let Some(puncturing) = self.preamble_puncturing {
+            flags2 |= 0x0400 | (u16::from(puncturing) & 0x0003) << 8;
+        }
+
+        let mut output = Vec::with_capacity(12);
+        output.extend_from_slice(&flags1.to_le_bytes());
+        output.extend_from_slice(&flags2.to_le_bytes());
+        output.extend_from_slice(&self.ru_channel1.unwrap_or_default());
+        output.extend_from_slice(&self.ru_channel2.unwrap_or_default());
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(12)
+    }
+}
+
+/// The presence of this field indicates that no PSDU was captured, only the
+/// PHY data.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZeroLengthPSDU {
+    /// The reason no PSDU was captured.
+    pub psdu_type: PSDUType,
+}
+
+impl Field for ZeroLengthPSDU {
+    fn from_bytes(input: &[u8]) -> Result<ZeroLengthPSDU> {
+        let psdu_type = PSDUType::new(ByteReader::new(input).read_u8()?);
+        Ok(ZeroLengthPSDU { psdu_type })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.psdu_type.value()]
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+}
+
+/// The legacy signal (L-SIG) information. Unknown sub-fields are `None`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LSIG {
+    /// The L-SIG rate.
+    pub rate: Option<u8>,
+    /// The L-SIG length.
+    pub length: Option<u16>,
+}
+
+impl Field for LSIG {
+    fn from_bytes(input: &[u8]) -> Result<LSIG> {
+        let mut cursor = ByteReader::new(input);
+        let mut lsig = LSIG {
+            ..Default::default()
+        };
+
+        let data1 = cursor.read_u16()?;
+        let data2 = cursor.read_u16()?;
+
+        if data1.is_flag_set(0x0001) {
+            lsig.rate = Some((data2 & 0x000f) as u8);
+        }
+
+        if data1.is_flag_set(0x0002) {
+            lsig.length = Some((data2 & 0xfff0) >> 4);
+        }
+
+        Ok(lsig)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data1: u16 = 0;
+        let mut data2: u16 = 0;
+
+        if let Some(rate) = self.rate {
+            data1 |= 0x0001;
+            data2 |= u16::from(rate) & 0x000f;
+        }
+
+        if let Some(length) = self.length {
+            data1 |= 0x0002;
+            data2 |= (length & 0x0fff) << 4;
+        }
+
+        let mut output = Vec::with_capacity(4);
+        output.extend_from_slice(&data1.to_le_bytes());
+        output.extend_from_slice(&data2.to_le_bytes());
+        output
+    }
+
+    fn size() -> Option<usize> {
+        Some(4)
+    }
+}
+
+/// The IEEE 802.11be U-SIG information. Unknown sub-fields are `None`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct USIG {
+    /// The PHY version identifier.
+    pub phy_version: Option<u8>,
+    /// The bandwidth.
+    pub bw: Option<EHTBandwidth>,
+    /// Whether the PPDU is sent to an AP (uplink).
+    pub ul_dl: Option<bool>,
+    /// The BSS color.
+    pub bss_color: Option<u8>,
+    /// The TXOP duration.
+    pub txop: Option<u8>,
+    /// The U-SIG CRC check failed.
+    pub bad_crc: bool,
+    /// Whether the validate bits were as expected, if they were checked.
+    pub validate_ok: Option<bool>,
+    /// The PPDU type and compression mode.
+    pub ppdu_type: Option<u8>,
+    /// The raw U-SIG-1 and U-SIG-2 bits following the common fields, which
+    /// depend on the PPDU type.
+    pub value: u32,
+    /// The known bits of the value.
+    pub mask: u32,
+}
+
+impl Field for USIG {
+    fn from_bytes(input: &[u8]) -> Result<USIG> {
+        let mut cursor = ByteReader::new(input);
+        let common = cursor.read_u32()?;
+        let value = cursor.read_u32()?;
+        let mask = cursor.read_u32()?;
+
+        let mut usig = USIG {
+            phy_version: None,
+            bw: None,
+            ul_dl: None,
+            bss_color: None,
+            txop: None,
+            bad_crc: common.is_flag_set(0x0000_0020),
+            validate_ok: None,
+            ppdu_type: None,
+            value,
+            mask,
+        };
+
+        if common.is_flag_set(0x0000_0001) {
+            usig.phy_version = Some(common.bits_as_int(12, 3) as u8);
+        }
+
+        if common.is_flag_set(0x0000_0002) {
+            usig.bw = Some(EHTBandwidth::new(common.bits_as_int(15, 3) as u8)?);
+        }
+
+        if common.is_flag_set(0x0000_0004) {
+            usig.ul_dl = Some(common.is_flag_set(0x0004_0000));
+        }
+
+        if common.is_flag_set(0x0000_0008) {
+            usig.bss_color = Some(common.bits_as_int(19, 6) as u8);
+        }
+
+        if common.is_flag_set(0x0000_0010) {
+            usig.txop = Some(common.bits_as_int(25, 7) as u8);
+        }
+
+        if common.is_flag_set(0x0000_0040) {
+            usig.validate_ok = Some(common.is_flag_set(0x0000_0080));
+        }
+
+        if mask & 0x0000_00c0 == 0x0000_00c0 {
+            usig.ppdu_type = Some(((value & 0x0000_00c0) >> 6) as u8);
+        }
+
+        Ok(usig)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut common: u32 = 0;
+        let mut value = self.value;
+        let mut mask = self.mask;
+
+        if let Some(phy_version) = self.phy_version {
+            common |= 0x0000_0001 | (u32::from(phy_version) & 0x07) << 12;
+        }
+
+        if let Some(bw) = self.bw {
+            common |= 0x0000_0002 | u32::from(bw.value()) << 15;
+        }
+
+        if let Some(ul_dl) = self.ul_dl {
+            common |= 0x0000_0004;
+            if ul_dl {
+                common |= 0x0004_0000;
+            }
+        }
+
+        if let Some(bss_color) = self.bss_color {
+            common |= 0x0000_0008 | (u32::from(bss_color) & 0x3f) << 19;
+        }
+
+        if let Some(txop) = self.txop {
+            common |= 0x0000_0010 | (u32::from(txop) & 0x7f) << 25;
+        }
+
+        if self.bad_crc {
+            common |= 0x0000_0020;
+        }
+
+        if let Some(validate_ok) = self.validate_ok {
+            common |= 0x0000_0040;
+            if validate_ok {
+                common |= 0x0000_0080;
+            }
+        }
+
+        if let Some(ppdu_type) = self.ppdu_type {
+            value = (value & !0x0000_00c0) | (u32::from(ppdu_type) & 0x03) << 6;
+            mask |= 0x0000_00c0;
+        }
+
+        let mut output = Vec::with_capacity(12);
+        output.extend_from_slice(&common.to_le_bytes());
+        output.extend_from_slice(&value.to_le_bytes());
+        output.extend_from_slice(&mask.to_le_bytes());
+        output
+    }
+}
+
+/// The IEEE 802.11be EHT information. Unknown sub-fields are `None`.
+///
+/// The field has a fixed portion of a known word and nine data words, followed
+/// by a user info word for each user.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EHT {
+    /// The spatial reuse value.
+    pub spatial_reuse: Option<u8>,
+    /// The guard interval.
+    pub gi: Option<HEGuardInterval>,
+    /// Whether the LDPC encoding process resulted in an extra OFDM symbol
+    /// segment.
+    pub ldpc_extra: Option<bool>,
+    /// The packet extension disambiguity.
+    pub pe_disambiguity: Option<bool>,
+    /// The RU or MRU size.
+    pub ru_size: Option<u8>,
+    /// The RU or MRU index.
+    pub ru_index: Option<u8>,
+    /// The RU allocations of the EHT-SIG content channels, in the order they
+    /// are given in the field.
+    pub ru_allocation: [Option<u16>; 16],
+    /// The primary 80 MHz channel position.
+    pub primary_80: Option<u8>,
+    /// Number of spatial streams, for a single user.
+    pub nss: Option<u8>,
+    /// Whether transmit beamforming is used, for a single user.
+    pub beamformed: Option<bool>,
+    /// The number of non-OFDMA users.
+    pub non_ofdma_users: Option<u8>,
+    /// The users.
+    pub users: Vec<EHTUser>,
+}
+
+/// The data word and bit offset of each EHT RU allocation, each is 9 bits
+/// followed by its known bit.
+const EHT_RU_ALLOCATION: [(usize, u8); 16] = [
+    (1, 13),
+    (2, 0),
+    (2, 10),
+    (2, 20),
+    (3, 0),
+    (3, 10),
+    (3, 20),
+    (4, 0),
+    (4, 10),
+    (4, 20),
+    (5, 0),
+    (5, 10),
+    (5, 20),
+    (6, 0),
+    (6, 10),
+    (6, 20),
+];
+
+impl Field for EHT {
+    fn from_bytes(input: &[u8]) -> Result<EHT> {
+        let mut cursor = ByteReader::new(input);
+        let known = cursor.read_u32()?;
+        let mut data = [0u32; 9];
+        for word in data.iter_mut() {
+            *word = cursor.read_u32()?;
+        }
+
+        let mut eht = EHT::default();
+
+        if known.is_flag_set(0x0000_0002) {
+            eht.spatial_reuse = Some(data[0].bits_as_int(3, 4) as u8);
+        }
+
+        if known.is_flag_set(0x0000_0004) {
+            eht.gi = Some(HEGuardInterval::new(data[0].bits_as_int(7, 2) as u8)?);
+        }
+
+        if known.is_flag_set(0x0000_0020) {
+            eht.ldpc_extra = Some(data[0].is_flag_set(0x0000_2000));
+        }
+
+        if known.is_flag_set(0x0000_0080) {
+            eht.pe_disambiguity = Some(data[0].is_flag_set(0x0001_0000));
+        }
+
+        if known.is_flag_set(0x0002_0000) {
+            eht.nss = Some(data[7].bits_as_int(12, 4) as u8);
+        }
+
+        if known.is_flag_set(0x0004_0000) {
+            eht.beamformed = Some(data[7].is_flag_set(0x0001_0000));
+        }
+
+        if known.is_flag_set(0x0008_0000) {
+            eht.non_ofdma_users = Some(data[7].bits_as_int(17, 3) as u8);
+        }
+
+        if known.is_flag_set(0x0040_0000) {
+            eht.ru_size = Some(data[1].bits_as_int(0, 5) as u8);
+        }
+
+        if known.is_flag_set(0x0080_0000) {
+            eht.ru_index = Some(data[1].bits_as_int(5, 8) as u8);
+        }
+
+        if known.is_flag_set(0x0200_0000) {
+            eht.primary_80 = Some(data[1].bits_as_int(30, 2) as u8);
+        }
+
+        for (allocation, &(word, offset)) in eht.ru_allocation.iter_mut().zip(&EHT_RU_ALLOCATION) {
+            if data[word].is_bit_set(offset + 9) {
+                *allocation = Some(data[word].bits_as_int(offset, 9) as u16);
+            }
+        }
+
+        while cursor.position() + 4 <= input.len() {
+            eht.users.push(EHTUser::new(cursor.read_u32()?));
+        }
+
+        Ok(eht)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut known: u32 = 0;
+        let mut data = [0u32; 9];
+
+        if let Some(spatial_reuse) = self.spatial_reuse {
+            known |= 0x0000_0002;
+            data[0] |= (u32::from(spatial_reuse) & 0x0f) << 3;
+        }
+
+        if let Some(gi) = self.gi {
+            known |= 0x0000_0004;
+            data[0] |= u32::from(gi.value()) << 7;
+        }
+
+        let bools = [
+            (self.ldpc_extra, 0x0000_0020, 0, 0x0000_2000),
+            (self.pe_disambiguity, 0x0000_0080, 0, 0x0001_0000),
+            (self.beamformed, 0x0004_0000, 7, 0x0001_0000),
+        ];
+        for &(value, flag, word, bit) in bools.iter() {
+            if let Some(value) = value {
+                known |= flag;
+                if value {
+                    data[word] |= bit;
+                }
+            }
+        }
+
+        let values = [
+            (self.nss, 0x0002_0000, 7, 12, 0x0f),
+            (self.non_ofdma_users, 0x0008_0000, 7, 17, 0x07),
+            (self.ru_size, 0x0040_0000, 1, 0, 0x1f),
+            (self.ru_index, 0x0080_0000, 1, 5, 0xff),
+            (self.primary_80, 0x0200_0000, 1, 30, 0x03),
+        ];
+        for &(value, flag, word, offset, mask) in values.iter() {
+            if let Some(value) = value {
+                known |= flag;
+                data[word] |= (u32::from(value) & mask) << offset;
+            }
+        }
+
+        for (allocation, &(word, offset)) in self.ru_allocation.iter().zip(&EHT_RU_ALLOCATION) {
+            if let Some(allocation) = allocation {
+                data[word] |= (u32::from(*allocation) & 0x01ff | 0x0200) << offset;
+            }
+        }
+
+        let mut output = Vec::with_capacity(40 + 4 * self.users.len());
+        output.extend_from_slice(&known.to_le_bytes());
+        for word in data.iter() {
+            output.extend_from_slice(&word.to_le_bytes());
+        }
+        for user in &self.users {
+            output.extend_from_slice(&user.value().to_le_bytes());
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+
+    /// Asserts that the field parsed from the input survives a round trip.
+    fn roundtrip<T: Field + Debug + PartialEq>(input: &[u8]) {
+        let field = T::from_bytes(input).unwrap();
+        assert_eq!(T::from_bytes(&field.to_bytes()).unwrap(), field);
+    }
+
+    #[test]
+    fn header() {
+        let input = [0, 0, 16, 0, 6, 0, 0, 192, 1, 0, 0, 160, 32, 0, 0, 0];
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.to_bytes(), input);
+
+        // AntennaSignal, then Antenna in the next radiotap namespace
+        let input = [0, 0, 12, 0, 32, 0, 0, 160, 0, 8, 0, 0];
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.resets, [1]);
+        assert_eq!(header.to_bytes(), input);
+    }
+
+    #[test]
+    fn header_peek_length() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        assert_eq!(Header::peek_length(&frame).unwrap(), 56);
+        assert_eq!(Header::peek_length(&frame[..4]).unwrap(), 56);
+
+        match Header::peek_length(&frame[..3]) {
+            Err(Error::IncompleteError { .. }) => {}
+            result => panic!("Error not IncompleteError: {:?}", result),
+        }
+        match Header::peek_length(&[0, 0, 4, 0]) {
+            Err(Error::InvalidLength) => {}
+            result => panic!("Error not InvalidLength: {:?}", result),
+        }
+        match Header::peek_length(&[1, 0, 56, 0]) {
+            Err(Error::UnsupportedVersion) => {}
+            result => panic!("Error not UnsupportedVersion: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn encode_header() {
+        // Flags, padding, and Channel
+        let mut input = super::encode_header(&[0x0000_000a], 6);
+        input.extend_from_slice(&[0x10, 0, 0x6c, 0x09, 0xa0, 0x00]);
+        assert_eq!(input[..8], [0, 0, 14, 0, 10, 0, 0, 0]);
+
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.length, 14);
+        assert_eq!(header.present[..], [Kind::Flags, Kind::Channel]);
+
+        // Switching to a vendor namespace and back
+        let input = super::encode_header(&[0x4000_0002, 0x2000_0000, 0x0000_0004], 0);
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.size, 16);
+        assert_eq!(header.to_bytes(), input[..16]);
+    }
+
+    #[test]
+    fn header_present_mask() {
+        let header = Header::from_bytes(&[0, 0, 8, 0, 10, 0, 0, 0]).unwrap();
+        let mask = header.present_mask();
+        assert!(mask.is_set(1));
+        assert!(mask.is_set(3));
+        assert!(!mask.is_set(21));
+        assert!(!mask.is_set(64));
+        assert_eq!(mask.iter_bits().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(mask.to_string(), "Flags, Channel");
+
+        // Flags, HE-MU-other-user, and an unknown field in bit 32
+        let header = Header::from_bytes(&[0, 0, 12, 0, 2, 0, 0, 130, 1, 0, 0, 0]).unwrap();
+        assert_eq!(header.present_mask(), PresentMask(0x1_0200_0002));
+        assert_eq!(header.present_mask().to_string(), "Flags, bit 25, bit 32");
+    }
+
+    #[test]
+    fn kind_name() {
+        let kinds = (0..64).filter_map(|bit| Kind::new(bit).ok()).chain([
+            Kind::USIG,
+            Kind::EHT,
+            Kind::VendorNamespace(None),
+        ]);
+        for kind in kinds {
+            assert_eq!(kind.name().parse::<Kind>().unwrap(), kind);
+        }
+
+        assert_eq!(Kind::AMPDUStatus.name(), "A-MPDU status");
+        assert_eq!(Kind::TLV(40).name(), "TLV");
+        match "TLV".parse::<Kind>() {
+            Err(Error::UnsupportedField) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn header_has_field() {
+        let input = [0, 0, 14, 0, 12, 0, 0, 0, 12, 0, 108, 9, 160, 0];
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.present[..], [Kind::Rate, Kind::Channel]);
+        assert!(header.has_field(Kind::Rate));
+        assert!(header.has_field(Kind::Channel));
+        assert!(!header.has_field(Kind::VHT));
+        assert!(!header.has_field(Kind::VendorNamespace(None)));
+
+        let input = [0, 0, 16, 0, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0];
+        let header = Header::from_bytes(&input).unwrap();
+        assert!(header.has_field(Kind::VendorNamespace(None)));
+    }
+
+    #[test]
+    fn kind_unparsed() {
+        let kind = Kind::new(25).unwrap();
+        assert_eq!(kind, Kind::Unparsed(25));
+        assert_eq!((kind.bit(), kind.align(), kind.size()), (25, 2, 6));
+        assert!(matches!(Kind::new(32), Err(Error::UnsupportedField)));
+    }
+
+    #[test]
+    fn header_present_words() {
+        // A second radiotap namespace starts again at bit 0, so the Channel
+        // field in the second word is bit 3
+        let input = [0, 0, 12, 0, 2, 0, 0, 160, 8, 0, 0, 0];
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.present[..], [Kind::Flags, Kind::Channel]);
+        assert!(header.skipped.is_empty());
+
+        // Without a namespace switch the second word continues at bit 32, bit
+        // 35 is not the Channel field
+        let input = [0, 0, 12, 0, 2, 0, 0, 128, 8, 0, 0, 0];
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.present[..], [Kind::Flags]);
+        assert_eq!(header.skipped, [35]);
+        assert!(Kind::new(35).is_err());
+    }
+
+    #[test]
+    fn header_tlv() {
+        let input = [0, 0, 8, 0, 34, 0, 0, 16];
+        let header = Header::from_bytes(&input).unwrap();
+        assert_eq!(header.present[..], [Kind::Flags, Kind::AntennaSignal]);
+        assert!(header.tlv);
+        assert_eq!(header.to_bytes(), input);
+
+        // Bit 28 is not the TLV bit in a vendor namespace
+        let input = [0, 0, 12, 0, 0, 0, 0, 64, 0, 0, 0, 16];
+        let header = Header::from_bytes(&input).unwrap();
+        assert!(!header.tlv);
+    }
+
+    #[test]
+    fn vendor_namespace() {
+        let input = [0, 17, 34, 1, 8, 0];
+        assert_eq!(
+            VendorNamespace::from_bytes(&input).unwrap().to_bytes(),
+            input
+        );
+    }
+
+    #[test]
+    fn vendor_namespace_truncated() {
+        match VendorNamespace::from_bytes(&[0, 17, 34, 1]) {
+            Err(Error::IncompleteError { offset: 4, .. }) => {}
+            result => panic!("Error not IncompleteError: {:?}", result),
+        }
+        match VendorNamespace::from_bytes(&[0, 17]) {
+            Err(Error::IncompleteError { offset: 0, .. }) => {}
+            result => panic!("Error not IncompleteError: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn guard_interval_nanos() {
+        assert_eq!(GuardInterval::Long.nanos(), 800);
+        assert_eq!(GuardInterval::Short.nanos(), 400);
+        assert_eq!(HEGuardInterval::Ns800.nanos(), 800);
+        assert_eq!(HEGuardInterval::Ns1600.nanos(), 1600);
+        assert_eq!(HEGuardInterval::Ns3200.nanos(), 3200);
+    }
+
+    #[test]
+    fn rate_raw() {
+        let rate = Rate::from_bytes(&[3]).unwrap();
+        assert_eq!(rate.value, 1.5);
+        assert_eq!(rate.raw(), 3);
+        assert_eq!(Rate { value: 54.0 }.raw(), 108);
+        roundtrip::<Rate>(&[3]);
+    }
+
+    #[test]
+    fn antenna_signal_quality() {
+        let quality = |value| AntennaSignal { value }.quality_percent();
+        assert_eq!(quality(-40), 100);
+        assert_eq!(quality(-50), 100);
+        assert_eq!(quality(-70), 60);
+        assert_eq!(quality(-95), 10);
+        assert_eq!(quality(-100), 0);
+        assert_eq!(quality(-128), 0);
+        assert_eq!(quality(127), 100);
+    }
+
+    #[test]
+    fn from_bytes_size() {
+        let channel = [0x3c, 0x14, 0x40, 0x01];
+        assert!(from_bytes::<Channel>(&channel).is_ok());
+
+        let oversized = [0x3c, 0x14, 0x40, 0x01, 0x00];
+        match from_bytes::<Channel>(&oversized) {
+            Err(Error::InvalidFormat { .. }) => {}
+            result => panic!("Error not InvalidFormat: {:?}", result),
+        }
+        match from_bytes_some::<Channel>(&channel[..3]) {
+            Err(Error::InvalidFormat { .. }) => {}
+            result => panic!("Error not InvalidFormat: {:?}", result),
+        }
+
+        // Fields that vary in size are not checked
+        assert_eq!(<Header as Field>::size(), None);
+        assert!(from_bytes::<Header>(&[0, 0, 8, 0, 0, 0, 0, 0, 0xff]).is_ok());
+    }
+
+    #[test]
+    fn simple_fields() {
+        roundtrip::<TSFT>(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        roundtrip::<Flags>(&[0x12]);
+        roundtrip::<Rate>(&[0x0c]);
+        roundtrip::<Channel>(&[0x3c, 0x14, 0x40, 0x01]);
+        roundtrip::<FHSS>(&[1, 2]);
+        roundtrip::<AntennaSignal>(&[0xb8]);
+        roundtrip::<AntennaSignalDb>(&[40]);
+        roundtrip::<AntennaNoise>(&[0x9c]);
+        roundtrip::<AntennaNoiseDb>(&[10]);
+        roundtrip::<LockQuality>(&[50, 0]);
+        roundtrip::<TxAttenuation>(&[3, 0]);
+        roundtrip::<TxAttenuationDb>(&[4, 0]);
+        roundtrip::<TxPower>(&[0xf6]);
+        roundtrip::<Antenna>(&[1]);
+        roundtrip::<RxFlags>(&[2, 0]);
+        roundtrip::<TxFlags>(&[0x15, 0]);
+        roundtrip::<TxFlags>(&[0x3f, 0]);
+        roundtrip::<RTSRetries>(&[2]);
+        roundtrip::<DataRetries>(&[3]);
+        roundtrip::<ZeroLengthPSDU>(&[1]);
+        roundtrip::<ZeroLengthPSDU>(&[7]);
+        roundtrip::<XChannel>(&[0x40, 0x01, 0, 0, 0x3c, 0x14, 36, 20]);
+    }
+
+    #[test]
+    fn channel_number() {
+        let channel = |freq, ghz2, ghz5| Channel {
+            freq,
+            flags: ChannelFlags {
+                turbo: false,
+                cck: false,
+                ofdm: true,
+                ghz2,
+                ghz5,
+                passive: false,
+                dynamic: false,
+                gfsk: false,
+            },
+        };
+
+        assert_eq!(channel(2412, true, false).channel_number(), Some(1));
+        assert_eq!(channel(2437, true, false).channel_number(), Some(6));
+        assert_eq!(channel(2462, true, false).channel_number(), Some(11));
+        assert_eq!(channel(2484, true, false).channel_number(), Some(14));
+        assert_eq!(channel(5180, false, true).channel_number(), Some(36));
+        assert_eq!(channel(5745, false, true).channel_number(), Some(149));
+        assert_eq!(channel(5955, false, true).channel_number(), Some(1));
+        assert_eq!(channel(6135, false, false).channel_number(), Some(37));
+        assert_eq!(channel(5935, false, false).channel_number(), Some(2));
+
+        // 6 GHz is inferred from the frequency, with or without the 5 GHz flag
+        assert_eq!(channel(5955, false, false).band(), Some(Band::GHz6));
+        assert_eq!(channel(5955, false, false).channel_number(), Some(1));
+        assert_eq!(channel(7115, false, true).channel_number(), Some(233));
+        assert_eq!(channel(5955, true, false).channel_number(), None);
+        assert_eq!(channel(5180, false, false).band(), Some(Band::GHz5));
+
+        // Without band hints
+        assert_eq!(channel(2437, false, false).channel_number(), Some(6));
+        assert_eq!(channel(5180, false, false).channel_number(), Some(36));
+
+        // Out of band or not matching the band hints
+        assert_eq!(channel(2413, true, false).channel_number(), None);
+        assert_eq!(channel(3000, false, false).channel_number(), None);
+        assert_eq!(channel(5960, false, false).channel_number(), None);
+        assert_eq!(channel(2437, false, true).channel_number(), None);
+        assert_eq!(channel(5180, true, false).channel_number(), None);
+    }
+
+    #[test]
+    fn channel_to_freq_roundtrip() {
+        let channels = [
+            (Band::GHz2, &[1, 6, 11, 13, 14][..]),
+            (Band::GHz5, &[36, 100, 149, 165, 177][..]),
+            (Band::GHz6, &[1, 2, 37, 93, 233][..]),
+        ];
+
+        for &(band, numbers) in channels.iter() {
+            for &number in numbers {
+                let freq = channel_to_freq(number, band).unwrap();
+                let channel = Channel {
+                    freq,
+                    flags: ChannelFlags {
+                        turbo: false,
+                        cck: false,
+                        ofdm: true,
+                        ghz2: band == Band::GHz2,
+                        ghz5: band != Band::GHz2,
+                        passive: false,
+                        dynamic: false,
+                        gfsk: false,
+                    },
+                };
+                assert_eq!(channel.channel_number(), Some(number));
+                assert_eq!(channel_to_freq(number, band), Some(channel.freq));
+            }
+        }
+
+        assert_eq!(freq_to_channel(5955, Band::GHz6), Some(1));
+        assert_eq!(freq_to_channel(5955, Band::GHz5), None);
+        assert_eq!(freq_to_channel(5960, Band::GHz6), None);
+        assert_eq!(freq_to_channel(2417, Band::GHz2), Some(2));
+        assert_eq!(freq_to_channel(2000, Band::GHz2), None);
+        assert_eq!(channel_to_freq(0, Band::GHz2), None);
+        assert_eq!(channel_to_freq(15, Band::GHz2), None);
+        assert_eq!(channel_to_freq(190, Band::GHz5), None);
+        assert_eq!(channel_to_freq(4, Band::GHz6), None);
+        assert_eq!(channel_to_freq(235, Band::GHz6), None);
+    }
+
+    #[test]
+    fn mcs() {
+        roundtrip::<MCS>(&[0x3f, 0x74, 7]);
+        roundtrip::<MCS>(&[0x00, 0x00, 0]);
+        roundtrip::<MCS>(&[0xff, 0xf4, 7]);
+    }
+
+    #[test]
+    fn mcs_bandwidth() {
+        // MCS 7, long GI, for each of the bandwidth codes
+        let mcs = |code| MCS::from_bytes(&[0x07, code, 7]).unwrap();
+
+        let bw20 = mcs(0);
+        assert_eq!(bw20.bw.map(Bandwidth::occupied), Some(20));
+        assert_eq!(bw20.datarate, Some(65.0));
+
+        let bw40 = mcs(1);
+        assert_eq!(bw40.bw.map(Bandwidth::occupied), Some(40));
+        assert_eq!(bw40.datarate, Some(135.0));
+
+        let bw20l = mcs(2);
+        assert_eq!(
+            bw20l.bw,
+            Some(Bandwidth {
+                bandwidth: 40,
+                sideband: Some(20),
+                sideband_index: Some(0),
+            })
+        );
+        assert_eq!(bw20l.datarate, Some(65.0));
+
+        let bw20u = mcs(3);
+        assert_eq!(bw20u.bw.and_then(|bw| bw.sideband_index), Some(1));
+        assert_eq!(bw20u.datarate, Some(65.0));
+
+        roundtrip::<MCS>(&[0x07, 0x02, 7]);
+        roundtrip::<MCS>(&[0x07, 0x03, 7]);
+    }
+
+    #[test]
+    fn vht_bandwidth() {
+        // MCS 7, one spatial stream, long GI, rated by the occupied bandwidth
+        let datarate = |code| {
+            let vht = VHT::from_bytes(&[0x44, 0, 0, code, 0x71, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+            vht.users[0].unwrap().datarate
+        };
+
+        assert_eq!(datarate(4), Some(292.5));
+        // 40 MHz in the lower half of an 80 MHz channel
+        assert_eq!(datarate(5), Some(135.0));
+        // 20 MHz in the lowest quarter of an 80 MHz channel
+        assert_eq!(datarate(7), Some(65.0));
+        // 80 MHz in the lower half of a 160 MHz channel
+        assert_eq!(datarate(12), Some(292.5));
+        assert_eq!(datarate(11), Some(585.0));
+    }
+
+    #[test]
+    fn mcs_datarate_assuming_lgi() {
+        // MCS 7, 40 MHz, unknown GI
+        let mcs = MCS::from_bytes(&[0x03, 0x01, 7]).unwrap();
+        assert_eq!(mcs.gi, None);
+        assert_eq!(mcs.datarate, None);
+        assert_eq!(mcs.datarate_assuming_lgi(), Some(135.0));
+
+        // MCS 7, 40 MHz, short GI
+        let mcs = MCS::from_bytes(&[0x07, 0x05, 7]).unwrap();
+        assert_eq!(mcs.datarate_assuming_lgi(), Some(150.0));
+        assert_eq!(mcs.datarate_assuming_lgi(), mcs.datarate);
+
+        // Unknown bandwidth
+        let mcs = MCS::from_bytes(&[0x02, 0x00, 7]).unwrap();
+        assert_eq!(mcs.datarate_assuming_lgi(), None);
+    }
+
+    #[test]
+    fn mcs_ness() {
+        // The low bit of NESS is in the flags, the high bit is in known
+        let ness = |known, flags| MCS::from_bytes(&[known, flags, 0]).unwrap().ness;
+        assert_eq!(ness(0x00, 0x80), None);
+        assert_eq!(ness(0x42, 0x00), Some(0));
+        assert_eq!(ness(0x40, 0x80), Some(1));
+        assert_eq!(ness(0xc0, 0x00), Some(2));
+        assert_eq!(ness(0xc0, 0x80), Some(3));
+    }
+
+    #[test]
+    fn ht_rates() {
+        let bw20 = Bandwidth::new(0).unwrap();
+        let bw40 = Bandwidth::new(1).unwrap();
+        let rate = |index, bw, gi| ext::ht_rate(index, bw, gi).unwrap();
+
+        assert_eq!(rate(7, bw20, GuardInterval::Long), 65.0);
+        assert_eq!(rate(15, bw20, GuardInterval::Short), 144.4);
+        assert_eq!(rate(23, bw40, GuardInterval::Long), 405.0);
+        assert_eq!(rate(31, bw40, GuardInterval::Short), 600.0);
+        assert_eq!(rate(31, bw20, GuardInterval::Short), 288.9);
+
+        // Unequal modulation
+        assert_eq!(rate(33, bw20, GuardInterval::Long), 39.0);
+        assert_eq!(rate(76, bw40, GuardInterval::Short), 495.0);
+
+        // MCS 32 is only defined for 40 MHz
+        assert_eq!(rate(32, bw40, GuardInterval::Long), 6.0);
+        assert!(ext::ht_rate(32, bw20, GuardInterval::Long).is_err());
+        assert!(ext::ht_rate(77, bw20, GuardInterval::Long).is_err());
+    }
+
+    #[test]
+    fn little_endian() {
+        // Byte values that differ when read in either order, so a native-endian
+        // read fails on a big-endian host
+        let tsft = TSFT::from_bytes(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(tsft.value, 0x0807_0605_0403_0201);
+        let lock_quality = LockQuality::from_bytes(&[0x34, 0x12]).unwrap();
+        assert_eq!(lock_quality.value, 0x1234);
+        let channel = Channel::from_bytes(&[0x3c, 0x14, 0x40, 0x01]).unwrap();
+        assert_eq!(channel.freq, 5180);
+        assert!(channel.flags.ofdm && channel.flags.ghz5);
+        let ampdu_status = AMPDUStatus::from_bytes(&[1, 2, 3, 4, 0, 0, 0, 0]).unwrap();
+        assert_eq!(ampdu_status.reference, 0x0403_0201);
+
+        assert_eq!(tsft.to_bytes(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(lock_quality.to_bytes(), [0x34, 0x12]);
+        assert_eq!(channel.to_bytes(), [0x3c, 0x14, 0x40, 0x01]);
+    }
+
+    #[test]
+    fn tx_flags() {
+        let flags = TxFlags::from_bytes(&[0x20, 0]).unwrap();
+        assert!(flags.order);
+        assert!(!flags.fail && !flags.cts && !flags.rts && !flags.no_ack && !flags.no_seq);
+
+        let flags = TxFlags::from_bytes(&[0x0a, 0]).unwrap();
+        assert!(flags.cts && flags.no_ack);
+        assert!(!flags.order);
+    }
+
+    #[test]
+    fn ampdu_status() {
+        roundtrip::<AMPDUStatus>(&[1, 0, 0, 0, 0x0d, 0, 0, 0]);
+        roundtrip::<AMPDUStatus>(&[2, 0, 0, 0, 0x20, 0, 0x5a, 0]);
+        roundtrip::<AMPDUStatus>(&[3, 0, 0, 0, 0xf0, 0, 0x5a, 0]);
+        roundtrip::<AMPDUStatus>(&[4, 0, 0, 0, 0xd0, 0, 0, 0]);
+
+        let ampdu = AMPDUStatus::from_bytes(&[2, 0, 0, 0, 0x20, 0, 0x5a, 0]).unwrap();
+        assert_eq!(ampdu.delimiter_crc, Some(0x5a));
+        assert_eq!(ampdu.delimiter_crc_error, Some(false));
+        assert_eq!(ampdu.eof, None);
+
+        let ampdu = AMPDUStatus::from_bytes(&[2, 0, 0, 0, 0xb0, 0, 0x5a, 0]).unwrap();
+        assert_eq!(ampdu.delimiter_crc, Some(0x5a));
+        assert_eq!(ampdu.delimiter_crc_error, Some(true));
+        assert_eq!(ampdu.eof, Some(false));
+
+        // The CRC error flag is independent of the CRC being known
+        let ampdu = AMPDUStatus::from_bytes(&[2, 0, 0, 0, 0xd0, 0, 0x5a, 0]).unwrap();
+        assert_eq!(ampdu.delimiter_crc, None);
+        assert_eq!(ampdu.delimiter_crc_error, Some(true));
+        assert_eq!(ampdu.eof, Some(true));
+    }
+
+    #[test]
+    fn vht() {
+        roundtrip::<VHT>(&[0xc4, 0x01, 0x04, 4, 0x92, 0, 0, 0, 0, 1, 0x34, 0x12]);
+    }
+
+    #[test]
+    fn bandwidth_mhz() {
+        let mhz = |value| Bandwidth::new(value).unwrap().mhz();
+        assert_eq!(
+            [mhz(0), mhz(1), mhz(3), mhz(4), mhz(10), mhz(11), mhz(25)],
+            [20, 40, 40, 80, 80, 160, 160]
+        );
+
+        let mhz = |value| HEBandwidth::new(value).unwrap().mhz();
+        let widths: Vec<u16> = (0..=10).map(mhz).collect();
+        assert_eq!(widths, [20, 40, 80, 160, 2, 4, 8, 20, 40, 80, 160]);
+
+        let mhz = |value| EHTBandwidth::new(value).unwrap().mhz();
+        let widths: Vec<u16> = (0..=5).map(mhz).collect();
+        assert_eq!(widths, [20, 40, 80, 160, 320, 320]);
+    }
+
+    #[test]
+    fn vht_nsts() {
+        let nsts = |known, flags| {
+            let vht = VHT::from_bytes(&[known, 0, flags, 0, 0x12, 0x13, 0, 0, 0, 0, 0, 0]);
+            let users = vht.unwrap().users;
+            (users[0].unwrap().nsts, users[1].unwrap().nsts)
+        };
+        // STBC
+        assert_eq!(nsts(0x01, 0x01), (4, 6));
+        // No STBC
+        assert_eq!(nsts(0x01, 0x00), (2, 3));
+        // Unknown STBC
+        assert_eq!(nsts(0x00, 0x01), (2, 3));
+    }
+
+    #[test]
+    fn xchannel_operating_channel() {
+        // HT40+ on channel 36
+        let xchannel = XChannel::from_bytes(&[0x40, 0x01, 0x02, 0, 0x3c, 0x14, 36, 20]).unwrap();
+        assert_eq!(xchannel.operating_channel(), 38);
+        assert_eq!(xchannel.center_freq(), 5190);
+
+        // HT40- on channel 40
+        let xchannel = XChannel::from_bytes(&[0x40, 0x01, 0x04, 0, 0x50, 0x14, 40, 20]).unwrap();
+        assert_eq!(xchannel.operating_channel(), 38);
+        assert_eq!(xchannel.center_freq(), 5190);
+
+        // HT20 on channel 36
+        let xchannel = XChannel::from_bytes(&[0x40, 0x01, 0x01, 0, 0x3c, 0x14, 36, 20]).unwrap();
+        assert_eq!(xchannel.operating_channel(), 36);
+        assert_eq!(xchannel.center_freq(), 5180);
+    }
+
+    #[test]
+    fn bandwidth() {
+        let bw = Bandwidth::new(4).unwrap();
+        assert_eq!(bw.bandwidth, 80);
+        assert_eq!(bw.sideband, None);
+        assert_eq!(bw.value(), Some(4));
+        assert_eq!(Bandwidth::new(11).unwrap().bandwidth, 160);
+
+        // 40 MHz in the upper half of an 80 MHz channel
+        let bw = Bandwidth::new(6).unwrap();
+        assert_eq!(
+            (bw.bandwidth, bw.sideband, bw.sideband_index),
+            (80, Some(40), Some(1))
+        );
+        assert_eq!(bw.occupied(), 40);
+        assert_eq!(bw.sideband_offset(), 40);
+
+        // 20 MHz at the top of a 160 MHz channel
+        let bw = Bandwidth::new(25).unwrap();
+        assert_eq!(bw.occupied(), 20);
+        assert_eq!(bw.sideband_offset(), 140);
+        assert_eq!(bw.value(), Some(25));
+
+        let vht = VHT::from_bytes(&[0x40, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(vht.bw.unwrap().occupied(), 40);
+        assert_eq!(vht.bw.unwrap().sideband_offset(), 0);
+
+        assert!(matches!(
+            Bandwidth::new(26),
+            Err(Error::InvalidFormat {
+                value: Some(26),
+                ..
+            })
+        ));
+        assert!(matches!(
+            VHT::from_bytes(&[0x40, 0, 0, 26, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(Error::InvalidFormat {
+                value: Some(26),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn he() {
+        roundtrip::<HE>(&[252, 195, 118, 0, 42, 41, 0, 0, 130, 1, 2, 127]);
+        roundtrip::<HE>(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let he = HE::from_bytes(&[2, 0, 0, 0, 255, 255, 0, 0, 63, 255, 0, 255]).unwrap();
+        assert_eq!(he.format, HEFormat::MU);
+        assert_eq!(he.bss_color, None);
+        assert_eq!(he.bw, None);
+        assert_eq!(he.gi, None);
+        assert_eq!(he.txop, None);
+    }
+
+    #[test]
+    fn he_mu() {
+        roundtrip::<HEMU>(&[82, 209, 37, 0, 96, 0, 0, 0, 0, 0, 0, 0]);
+        roundtrip::<HEMU>(&[255, 255, 255, 15, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let he_mu = HEMU::from_bytes(&[15, 0, 3, 0, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(
+            he_mu,
+            HEMU {
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn lsig() {
+        let lsig = LSIG::from_bytes(&[3, 0, 0xdb, 0x10]).unwrap();
+        assert_eq!(
+            lsig,
+            LSIG {
+                rate: Some(11),
+                length: Some(269),
+            }
+        );
+        assert_eq!(lsig.to_bytes(), [3, 0, 0xdb, 0x10]);
+
+        roundtrip::<LSIG>(&[2, 0, 0xff, 0xff]);
+        roundtrip::<LSIG>(&[0, 0, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn usig() {
+        // 320 MHz-1, BSS color 42, TXOP 16, validate bits OK, PPDU type 0
+        let input = [
+            0xdf, 0x00, 0x52, 0x21, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00,
+        ];
+        let usig = USIG::from_bytes(&input).unwrap();
+        assert_eq!(usig.phy_version, Some(0));
+        assert_eq!(usig.bw, Some(EHTBandwidth::BW320Ch1));
+        assert_eq!(usig.ul_dl, Some(false));
+        assert_eq!(usig.bss_color, Some(42));
+        assert_eq!(usig.txop, Some(16));
+        assert!(!usig.bad_crc);
+        assert_eq!(usig.validate_ok, Some(true));
+        assert_eq!(usig.ppdu_type, Some(0));
+        assert_eq!(usig.to_bytes(), input);
+
+        // Nothing known
+        let usig = USIG::from_bytes(&[0; 12]).unwrap();
+        assert_eq!(usig.bw, None);
+        assert_eq!(usig.bss_color, None);
+        assert_eq!(usig.validate_ok, None);
+        assert_eq!(usig.ppdu_type, None);
+
+        roundtrip::<USIG>(&[
+            0xff, 0x70, 0x5e, 0xff, 0x45, 0x23, 0x01, 0x00, 0xff, 0x00, 0xf0, 0x0f,
+        ]);
+    }
+
+    #[test]
+    fn eht() {
+        // A single user with EHT-MCS 13, 2 spatial streams, and LDPC
+        let mut words = [0u32; 11];
+        words[0] = 0x00c6_0004;
+        words[1] = 0x0000_0080;
+        words[2] = 0x0000_0043;
+        words[8] = 0x0001_2000;
+        words[10] = 0x22d8_05b7;
+        let input: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+
+        let eht = EHT::from_bytes(&input).unwrap();
+        assert_eq!(eht.gi, Some(HEGuardInterval::Ns1600));
+        assert_eq!(eht.ru_size, Some(3));
+        assert_eq!(eht.ru_index, Some(2));
+        assert_eq!(eht.nss, Some(2));
+        assert_eq!(eht.beamformed, Some(true));
+        assert_eq!(eht.spatial_reuse, None);
+        assert_eq!(eht.ru_allocation, [None; 16]);
+        assert_eq!(
+            eht.users,
+            [EHTUser {
+                sta_id: Some(5),
+                index: Some(13),
+                fec: Some(FEC::LDPC),
+                nss: Some(2),
+                beamformed: Some(true),
+                spatial_config: None,
+                data_for_user: true,
+            }]
+        );
+        assert_eq!(eht.to_bytes(), input);
+
+        // The fixed portion is required
+        assert!(EHT::from_bytes(&input[..36]).is_err());
+    }
+
+    #[test]
+    fn vht_index() {
+        // Bandwidth and guard interval known, 20 MHz and long guard interval
+        let vht = VHT::from_bytes(&[0x44, 0, 0, 0, 0x53, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        let user = vht.users[0].unwrap();
+        assert_eq!(user.index, 5);
+        assert_eq!(user.nss, 3);
+        assert_eq!(
+            user.datarate,
+            Some(vht_rate(5, Bandwidth::new(0).unwrap(), GuardInterval::Long, 3).unwrap())
+        );
+        assert_eq!(vht.users[1], None);
+    }
+
+    #[test]
+    fn vht_rates() {
+        let bw20 = Bandwidth::new(0).unwrap();
+        let bw80 = Bandwidth::new(4).unwrap();
+        let rate = |index, bw, gi, nss| ext::vht_rate(index, bw, gi, nss).unwrap();
+
+        assert_eq!(rate(9, bw80, GuardInterval::Short, 1), 433.3);
+        assert_eq!(rate(0, bw20, GuardInterval::Long, 1), 6.5);
+        assert_eq!(rate(6, bw20, GuardInterval::Short, 2), 130.0);
+        assert_eq!(rate(9, bw20, GuardInterval::Long, 3), 260.0);
+        assert_eq!(rate(7, bw20, GuardInterval::Long, 8), 520.0);
+
+        // Not allowed by the standard
+        assert!(ext::vht_rate(9, bw20, GuardInterval::Long, 1).is_err());
+        assert!(ext::vht_rate(6, bw80, GuardInterval::Short, 3).is_err());
+        assert!(ext::vht_rate(0, bw20, GuardInterval::Long, 0).is_err());
+        assert!(ext::vht_rate(10, bw20, GuardInterval::Long, 1).is_err());
+    }
+
+    #[test]
+    fn vht_fec() {
+        let vht = VHT::from_bytes(&[0, 0, 0, 0, 0x11, 0x21, 0x31, 0x41, 0b1010, 0, 0, 0]).unwrap();
+        let fec: Vec<_> = vht.users.iter().map(|user| user.unwrap().fec).collect();
+        assert_eq!(fec, [FEC::BCC, FEC::LDPC, FEC::BCC, FEC::LDPC]);
+
+        let vht = VHT::from_bytes(&[0, 0, 0, 0, 0x11, 0x21, 0x31, 0x41, 0b0101, 0, 0, 0]).unwrap();
+        let fec: Vec<_> = vht.users.iter().map(|user| user.unwrap().fec).collect();
+        assert_eq!(fec, [FEC::LDPC, FEC::BCC, FEC::LDPC, FEC::BCC]);
+
+        roundtrip::<VHT>(&[0, 0, 0, 0, 0x11, 0x21, 0x31, 0x41, 0b1010, 0, 0, 0]);
+    }
+
+    #[test]
+    fn timestamp() {
+        roundtrip::<Timestamp>(&[1, 2, 3, 4, 5, 6, 7, 8, 10, 0, 0x11, 0x02]);
+        roundtrip::<Timestamp>(&[1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0x00, 0x00]);
+        roundtrip::<Timestamp>(&[1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0xf2, 0x00]);
+        roundtrip::<Timestamp>(&[1, 2, 3, 4, 0, 0, 0, 0, 5, 0, 0x11, 0x03]);
+    }
+
+    #[test]
+    fn timestamp_flags() {
+        let timestamp =
+            |flags| Timestamp::from_bytes(&[1, 2, 3, 4, 0, 0, 0, 0, 10, 0, 0x11, flags]).unwrap();
+
+        let known = timestamp(0x02);
+        assert_eq!(known.accuracy, Some(10));
+        assert!(!known.counter_32bit);
+
+        let unknown = timestamp(0x00);
+        assert_eq!(unknown.accuracy, None);
+        assert!(!unknown.counter_32bit);
+
+        let counter = timestamp(0x01);
+        assert_eq!(counter.accuracy, None);
+        assert!(counter.counter_32bit);
+        assert_eq!(counter.timestamp, 0x0403_0201);
+
+        let both = timestamp(0x03);
+        assert_eq!(both.accuracy, Some(10));
+        assert!(both.counter_32bit);
+        assert_eq!(both.unit, TimeUnit::Microseconds);
+        assert_eq!(both.position, SamplingPosition::StartPLCP);
+    }
+
+    #[test]
+    fn timestamp_position() {
+        let timestamp = Timestamp::from_bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x31, 0]).unwrap();
+        assert_eq!(timestamp.unit, TimeUnit::Microseconds);
+        assert_eq!(timestamp.position, SamplingPosition::EndMPDU);
+        assert_eq!(timestamp.to_bytes()[10], 0x31);
+    }
 }
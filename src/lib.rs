@@ -20,6 +20,9 @@
 //! }
 //! ```
 //!
+//! Use `Radiotap::split(&packet)` to also get the 802.11 frame that follows
+//! the Radiotap capture.
+//!
 //! If you just want to parse a few specific fields from the Radiotap capture
 //! you can create an iterator using `RadiotapIterator::from_bytes(&capture)`:
 //!
@@ -44,62 +47,313 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Features
+//!
+//! The `std` feature is enabled by default. Without it the crate is `no_std`,
+//! but still requires an allocator.
+//!
+//! The `smallvec` feature stores the kinds of the present fields inline, which
+//! avoids an allocation for most captures.
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for the parsed
+//! [Radiotap](struct.Radiotap.html) and all of its fields.
+//!
+//! The `pcap` feature adds the [pcap](pcap/index.html) module for reading
+//! Radiotap captures from pcap and pcapng files, it requires `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+pub mod fcs;
 pub mod field;
+pub mod ns;
+pub mod payload;
+#[cfg(feature = "pcap")]
+pub mod pcap;
+mod reader;
 
-use std::{io::Cursor, result};
+pub use payload::{frame_control, FrameControl, FrameType};
 
-use quick_error::quick_error;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, mem, result};
 
-use crate::field::*;
+use crate::{
+    field::{
+        ext::{GuardInterval, MacTime, PhyField, PhyMode},
+        *,
+    },
+    ns::{CaptureNamespace, Namespace},
+    reader::ByteReader,
+};
 
-quick_error! {
-    /// All errors returned and used by the radiotap module.
-    #[derive(Debug)]
-    pub enum Error {
-        /// The internal cursor on the data returned an IO error.
-        ParseError(err: std::io::Error) {
-            from()
-            source(err)
-            description(err.description())
+/// All errors returned and used by the radiotap module.
+///
+/// ```
+/// use radiotap::{Error, Radiotap};
+///
+/// match Radiotap::from_bytes(&[1, 0, 8, 0, 0, 0, 0, 0]) {
+///     Err(Error::UnsupportedVersion) => {}
+///     result => panic!("unexpected result: {:?}", result),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// The internal cursor on the data returned an IO error.
+    #[cfg(feature = "std")]
+    ParseError(std::io::Error),
+    /// The given data is not a complete Radiotap capture. Contains the offset
+    /// and kind of the field that was cut short, if known.
+    IncompleteError { offset: usize, kind: Option<Kind> },
+    /// The given data is shorter than the amount specified in the Radiotap header.
+    InvalidLength,
+    /// The given data is not a valid Radiotap capture. Contains the offset and
+    /// kind of the invalid field, if known, and the value at fault, if it is a
+    /// reserved code.
+    InvalidFormat {
+        offset: usize,
+        kind: Option<Kind>,
+        value: Option<u8>,
+    },
+    /// Unsupported Radiotap header version.
+    UnsupportedVersion,
+    /// Unsupported Radiotap field.
+    UnsupportedField,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            Error::ParseError(err) => err.fmt(f),
+            Error::IncompleteError { offset, kind } => {
+                f.write_str("The given data is not a complete Radiotap capture")?;
+                fmt_context(f, *offset, *kind)
+            }
+            Error::InvalidLength => f.write_str(
+                "The given data is shorter than the amount specified in the Radiotap header",
+            ),
+            Error::InvalidFormat {
+                offset,
+                kind,
+                value,
+            } => {
+                f.write_str("The given data is not a valid Radiotap capture")?;
+                if let Some(value) = value {
+                    write!(f, ", reserved value {}", value)?;
+                }
+                fmt_context(f, *offset, *kind)
+            }
+            Error::UnsupportedVersion => f.write_str("Unsupported Radiotap header version"),
+            Error::UnsupportedField => f.write_str("Unsupported Radiotap field"),
         }
-        /// The given data is not a complete Radiotap capture.
-        IncompleteError {
-            display("The given data is not a complete Radiotap capture")
+    }
+}
+
+/// Fills the buffer from the reader, returning an `IncompleteError` at the
+/// offset the reader ended at, counting from the given offset of the buffer.
+#[cfg(feature = "std")]
+fn read_full<R: std::io::Read>(reader: &mut R, buf: &mut [u8], offset: usize) -> Result<()> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => {
+                return Err(Error::IncompleteError {
+                    offset: offset + read,
+                    kind: None,
+                })
+            }
+            Ok(n) => read += n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
         }
-        /// The given data is shorter than the amount specified in the Radiotap header.
-        InvalidLength {
-            display("The given data is shorter than the amount specified in the Radiotap header")
+    }
+    Ok(())
+}
+
+/// Writes the offset and kind of the field an error occurred in, if known.
+fn fmt_context(f: &mut fmt::Formatter, offset: usize, kind: Option<Kind>) -> fmt::Result {
+    match kind {
+        Some(kind) => write!(f, " ({:?} field at offset {})", kind, offset),
+        None => Ok(()),
+    }
+}
+
+impl Error {
+    /// Returns an `InvalidFormat` error without any context.
+    pub(crate) fn invalid_format() -> Error {
+        Error::InvalidFormat {
+            offset: 0,
+            kind: None,
+            value: None,
         }
-        /// The given data is not a valid Radiotap capture.
-        InvalidFormat {
-            display("The given data is not a valid Radiotap capture")
+    }
+
+    /// Returns an `InvalidFormat` error for the given reserved value, without
+    /// any other context.
+    pub(crate) fn reserved_value(value: u8) -> Error {
+        Error::InvalidFormat {
+            offset: 0,
+            kind: None,
+            value: Some(value),
         }
-        /// Unsupported Radiotap header version.
-        UnsupportedVersion {
-            display("Unsupported Radiotap header version")
+    }
+
+    /// Adds the context of the field at the given offset to an error returned
+    /// while parsing that field. Offsets in the error become relative to the
+    /// start of the capture.
+    fn context(self, start: usize, field: Kind) -> Error {
+        match self {
+            Error::IncompleteError { offset, kind } => Error::IncompleteError {
+                offset: start + offset,
+                kind: kind.or(Some(field)),
+            },
+            Error::InvalidFormat {
+                offset,
+                kind,
+                value,
+            } => Error::InvalidFormat {
+                offset: start + offset,
+                kind: kind.or(Some(field)),
+                value,
+            },
+            err => err,
         }
-        /// Unsupported Radiotap field.
-        UnsupportedField {
-            display("Unsupported Radiotap field")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::ParseError(err) => Some(err),
+            _ => None,
         }
     }
 }
 
-type Result<T> = result::Result<T, Error>;
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::ParseError(err)
+    }
+}
+
+/// The result type returned by the radiotap module.
+pub type Result<T> = result::Result<T, Error>;
+
+/// The kind of capture header at the start of a packet, as classified by
+/// [detect_header](fn.detect_header.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HeaderKind {
+    /// A Radiotap header.
+    Radiotap,
+    /// A Prism (wlan-ng) monitor mode header.
+    Prism,
+    /// An AVS (AbsoluteValue Systems) monitor mode header.
+    Avs,
+    /// None of the above.
+    Unknown,
+}
+
+/// Returns the kind of capture header at the start of the input, so captures
+/// with a Prism or AVS header can be told apart before parsing.
+///
+/// This is a heuristic on the leading bytes. A Radiotap header has version 0
+/// and a length that holds the first present word and fits in the input. A
+/// Prism header starts with the message code 0x41 or 0x44, and an AVS header
+/// with the big endian magic 0x80211001 or 0x80211002.
+///
+/// ```
+/// use radiotap::{detect_header, HeaderKind};
+///
+/// assert_eq!(detect_header(&[0, 0, 8, 0, 0, 0, 0, 0]), HeaderKind::Radiotap);
+/// assert_eq!(detect_header(&[0x44, 0, 0, 0, 144, 0, 0, 0]), HeaderKind::Prism);
+/// ```
+pub fn detect_header(input: &[u8]) -> HeaderKind {
+    if let Ok(length) = Header::peek_length(input) {
+        if input[1] == 0 && length <= input.len() {
+            return HeaderKind::Radiotap;
+        }
+    }
+
+    if input.len() < 4 {
+        return HeaderKind::Unknown;
+    }
+    let magic = [input[0], input[1], input[2], input[3]];
 
-/// A trait to align an offset to particular word size, usually 1, 2, 4, or 8.
-trait Align {
-    /// Aligns the offset to `align` size.
-    fn align(&mut self, align: u64);
+    // The Prism message code is in host byte order
+    if matches!(u32::from_le_bytes(magic), 0x41 | 0x44)
+        || matches!(u32::from_be_bytes(magic), 0x41 | 0x44)
+    {
+        HeaderKind::Prism
+    } else if matches!(u32::from_be_bytes(magic), 0x8021_1001 | 0x8021_1002) {
+        HeaderKind::Avs
+    } else {
+        HeaderKind::Unknown
+    }
 }
 
-impl<T> Align for Cursor<T> {
-    /// Aligns the Cursor position to `align` size.
-    fn align(&mut self, align: u64) {
-        let p = self.position();
-        self.set_position((p + align - 1) & !(align - 1));
+/// Passes each field in the radiotap namespace to a user supplied
+/// [Namespace](ns/trait.Namespace.html) and returns the data after the
+/// capture.
+///
+/// This decodes the capture into a custom struct holding only the fields that
+/// are needed. The fields are passed to `update` with their present bit, the
+/// `oui` and `layout` of the namespace are not used as the layout of the
+/// radiotap namespace is known. Vendor namespaces and TLV fields are skipped.
+///
+/// ```
+/// use radiotap::{field::Oui, ns::Namespace, parse_into, Result};
+///
+/// #[derive(Default)]
+/// struct Signal(Option<i8>);
+///
+/// impl Namespace for Signal {
+///     fn oui(&self) -> Oui {
+///         [0; 3]
+///     }
+///
+///     fn layout(&self, _bit: u8) -> Option<(u64, usize)> {
+///         None
+///     }
+///
+///     fn update(&mut self, bit: u8, data: &[u8]) -> Result<()> {
+///         if bit == 5 {
+///             self.0 = Some(data[0] as i8);
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut signal = Signal::default();
+/// let payload = parse_into(&[0, 0, 9, 0, 32, 0, 0, 0, 213, 0xd4], &mut signal).unwrap();
+/// assert_eq!(signal.0, Some(-43));
+/// assert_eq!(payload, [0xd4]);
+/// ```
+pub fn parse_into<'a, N: Namespace>(input: &'a [u8], namespace: &mut N) -> Result<&'a [u8]> {
+    let (iterator, rest) = RadiotapIterator::parse(input)?;
+
+    for result in &iterator {
+        let (kind, data) = result?;
+        match kind {
+            Kind::VendorNamespace(_)
+            | Kind::VendorField(_)
+            | Kind::TLV(_)
+            | Kind::USIG
+            | Kind::EHT => {}
+            kind => namespace.update(kind.bit(), data)?,
+        }
     }
+
+    Ok(rest)
 }
 
 /// Represents an unparsed Radiotap capture format, only the header field is
@@ -111,23 +365,180 @@ pub struct RadiotapIterator<'a> {
 }
 
 impl<'a> RadiotapIterator<'a> {
+    /// Returns a [RadiotapIterator](struct.RadiotapIterator.html) over the
+    /// fields of an input byte array.
+    ///
+    /// Iteration stops at the first field that is not supported, because the
+    /// position of the fields after it cannot be known. Iteration also stops
+    /// after the last present field, any bytes left before the end of the
+    /// header are padding.
     pub fn from_bytes(input: &'a [u8]) -> Result<RadiotapIterator<'a>> {
         Ok(RadiotapIterator::parse(input)?.0)
     }
 
+    /// Returns a [RadiotapIterator](struct.RadiotapIterator.html) and the
+    /// remaining data from an input byte array.
     pub fn parse(input: &'a [u8]) -> Result<(RadiotapIterator<'a>, &'a [u8])> {
         let header: Header = from_bytes(input)?;
         let (data, rest) = input.split_at(header.length);
         Ok((RadiotapIterator { header, data }, rest))
     }
+
+    /// Returns the parsed [Header](field/struct.Header.html), which can be used
+    /// to check which fields are present before iterating over them.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
 }
 
 /// An iterator over Radiotap fields.
-#[doc(hidden)]
-#[derive(Debug, Clone)]
+///
+/// Fields in a vendor namespace are only yielded if the vendor namespace has
+/// been registered with [vendor](#method.vendor), otherwise the entire vendor
+/// namespace is skipped. TLV fields are yielded last, with the kind given by
+/// [Kind::from_tlv](field/enum.Kind.html#method.from_tlv).
+///
+/// Standard fields that are not parsed are yielded with their raw data as
+/// `Kind::Unparsed(bit)`. The fields after a field with an unknown layout can
+/// not be located, so they are not yielded.
+#[derive(Debug)]
 pub struct RadiotapIteratorIntoIter<'a> {
-    present: Vec<Kind>,
-    cursor: Cursor<&'a [u8]>,
+    present: Present,
+    cursor: ByteReader<'a>,
+    namespaces: CaptureNamespace<'a>,
+    vendor: Option<(Oui, usize)>,
+    tlv: bool,
+    // The number of fields left in `present` at each radiotap namespace reset
+    resets: Vec<usize>,
+    namespace: usize,
+}
+
+impl<'a> RadiotapIteratorIntoIter<'a> {
+    fn new(iterator: &RadiotapIterator<'a>) -> RadiotapIteratorIntoIter<'a> {
+        let present: Present = iterator.header.present.iter().rev().cloned().collect();
+        let resets = iterator
+            .header
+            .resets
+            .iter()
+            .rev()
+            .map(|&reset| present.len() - reset)
+            .collect();
+        let mut cursor = ByteReader::new(iterator.data);
+        cursor.set_position(iterator.header.size);
+        RadiotapIteratorIntoIter {
+            present,
+            cursor,
+            namespaces: CaptureNamespace::new(),
+            vendor: None,
+            tlv: iterator.header.tlv,
+            resets,
+            namespace: 0,
+        }
+    }
+
+    /// Returns the index of the namespace of the last field yielded, counting
+    /// every radiotap and vendor namespace from 0.
+    pub fn namespace(&self) -> usize {
+        self.namespace
+    }
+
+    /// Registers a vendor namespace. The fields in this vendor namespace will
+    /// be passed to the namespace's `update` as they are iterated over.
+    pub fn vendor(mut self, namespace: &'a mut dyn Namespace) -> RadiotapIteratorIntoIter<'a> {
+        self.namespaces.register(namespace);
+        self
+    }
+
+    /// Skips the rest of the current vendor namespace.
+    fn skip_vendor(&mut self) {
+        while let Some(Kind::VendorField(_)) = self.present.last() {
+            self.present.pop();
+        }
+        if let Some((_, end)) = self.vendor.take() {
+            self.cursor.set_position(end);
+        }
+    }
+
+    /// Returns the next field in the current vendor namespace.
+    fn next_vendor_field(&mut self, bit: u8) -> Option<Result<(Kind, &'a [u8])>> {
+        let (oui, vendor_end) = match self.vendor {
+            Some(vendor) => vendor,
+            None => return self.next(),
+        };
+        let namespace = self.namespaces.get_mut(oui)?;
+
+        match namespace.layout(bit) {
+            Some((align, size)) => {
+                self.cursor.align(align);
+
+                let start = self.cursor.position();
+                // The vendor namespace lied about how long it was
+                let end = match start.checked_add(size) {
+                    Some(end) if end <= vendor_end => end,
+                    _ => {
+                        return Some(Err(Error::IncompleteError {
+                            offset: start,
+                            kind: Some(Kind::VendorField(bit)),
+                        }))
+                    }
+                };
+
+                let data = &self.cursor.get_ref()[start..end];
+                self.cursor.set_position(end);
+                match namespace.update(bit, data) {
+                    Ok(()) => Some(Ok((Kind::VendorField(bit), data))),
+                    Err(e) => Some(Err(e)),
+                }
+            }
+            None => {
+                // The vendor namespace doesn't know this field, so none of the
+                // fields after it can be located
+                self.skip_vendor();
+                self.next()
+            }
+        }
+    }
+
+    /// Returns the next TLV field, or `None` if there are no more.
+    fn next_tlv(&mut self) -> Option<Result<(Kind, &'a [u8])>> {
+        if !self.tlv {
+            return None;
+        }
+
+        // Leaving a vendor namespace, so skip whatever is left of it.
+        if let Some((_, end)) = self.vendor.take() {
+            self.cursor.set_position(end);
+        }
+
+        self.cursor.align(Kind::TLV(0).align());
+
+        let len = self.cursor.get_ref().len();
+        if len - self.cursor.position().min(len) < Kind::TLV(0).size() {
+            // Only padding is left
+            self.tlv = false;
+            return None;
+        }
+
+        let tlv_type = self.cursor.read_u16().ok()?;
+        let tlv_length = self.cursor.read_u16().ok()?;
+
+        let start = self.cursor.position();
+        // The TLV lied about how long it was
+        let end = match start.checked_add(tlv_length as usize) {
+            Some(end) if end <= len => end,
+            _ => {
+                self.tlv = false;
+                return Some(Err(Error::IncompleteError {
+                    offset: start - Kind::TLV(tlv_type).size(),
+                    kind: Some(Kind::TLV(tlv_type)),
+                }));
+            }
+        };
+
+        let data = &self.cursor.get_ref()[start..end];
+        self.cursor.set_position(end);
+        Some(Ok((Kind::from_tlv(tlv_type), data)))
+    }
 }
 
 impl<'a> IntoIterator for &'a RadiotapIterator<'a> {
@@ -135,10 +546,7 @@ impl<'a> IntoIterator for &'a RadiotapIterator<'a> {
     type Item = Result<(Kind, &'a [u8])>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let present = self.header.present.iter().rev().cloned().collect();
-        let mut cursor = Cursor::new(self.data);
-        cursor.set_position(self.header.size as u64);
-        RadiotapIteratorIntoIter { present, cursor }
+        RadiotapIteratorIntoIter::new(self)
     }
 }
 
@@ -147,10 +555,7 @@ impl<'a> IntoIterator for RadiotapIterator<'a> {
     type Item = Result<(Kind, &'a [u8])>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let present = self.header.present.iter().rev().cloned().collect();
-        let mut cursor = Cursor::new(self.data);
-        cursor.set_position(self.header.size as u64);
-        RadiotapIteratorIntoIter { present, cursor }
+        RadiotapIteratorIntoIter::new(&self)
     }
 }
 
@@ -158,36 +563,119 @@ impl<'a> Iterator for RadiotapIteratorIntoIter<'a> {
     type Item = Result<(Kind, &'a [u8])>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        while matches!(self.resets.last(), Some(&left) if left >= self.present.len()) {
+            self.resets.pop();
+            self.namespace += 1;
+        }
+
         match self.present.pop() {
+            Some(Kind::VendorField(bit)) => self.next_vendor_field(bit),
             Some(mut kind) => {
+                // Leaving a vendor namespace, so skip whatever is left of it.
+                if let Some((_, end)) = self.vendor.take() {
+                    self.cursor.set_position(end);
+                }
+
                 // Align the cursor to the current field's needed alignment.
                 self.cursor.align(kind.align());
 
-                let mut start = self.cursor.position() as usize;
-                let mut end = start + kind.size();
-
+                let len = self.cursor.get_ref().len();
+                let mut start = self.cursor.position();
                 // The header lied about how long the body was
-                if end > self.cursor.get_ref().len() {
-                    Some(Err(Error::IncompleteError))
-                } else {
-                    // Switching to a vendor namespace, and we don't know how to handle
-                    // so we just return the entire vendor namespace section
-                    if kind == Kind::VendorNamespace(None) {
-                        match VendorNamespace::from_bytes(&self.cursor.get_ref()[start..end]) {
-                            Ok(vns) => {
-                                start += kind.size();
-                                end += vns.skip_length as usize;
-                                kind = Kind::VendorNamespace(Some(vns));
+                let mut end = match start.checked_add(kind.size()) {
+                    Some(end) if end <= len => end,
+                    _ => {
+                        return Some(Err(Error::IncompleteError {
+                            offset: start,
+                            kind: Some(kind),
+                        }))
+                    }
+                };
+
+                // Switching to a vendor namespace, if we don't know how to
+                // handle it we just return the entire vendor namespace
+                // section and skip its fields
+                if kind == Kind::VendorNamespace(None) {
+                    self.namespace += 1;
+                    match VendorNamespace::from_bytes(&self.cursor.get_ref()[start..end]) {
+                        Ok(vns) => {
+                            start += kind.size();
+                            kind = Kind::VendorNamespace(Some(vns));
+                            end = match end.checked_add(vns.skip_length as usize) {
+                                Some(end) if end <= len => end,
+                                _ => {
+                                    return Some(Err(Error::IncompleteError {
+                                        offset: start - kind.size(),
+                                        kind: Some(kind),
+                                    }))
+                                }
+                            };
+                            if self.namespaces.contains(vns.oui) {
+                                // The fields will be read from the start
+                                self.vendor = Some((vns.oui, end));
+                                let data = &self.cursor.get_ref()[start..end];
+                                self.cursor.set_position(start);
+                                return Some(Ok((kind, data)));
                             }
-                            Err(e) => return Some(Err(e)),
+                            self.skip_vendor();
                         }
+                        Err(e) => return Some(Err(e)),
                     }
-                    let data = &self.cursor.get_ref()[start..end];
-                    self.cursor.set_position(end as u64);
-                    Some(Ok((kind, data)))
                 }
+                let data = &self.cursor.get_ref()[start..end];
+                self.cursor.set_position(end);
+                Some(Ok((kind, data)))
             }
-            None => None,
+            None => self.next_tlv(),
+        }
+    }
+}
+
+/// A field yielded by
+/// [RadiotapIteratorIntoIter](struct.RadiotapIteratorIntoIter.html) with its
+/// data copied, so it can outlive the capture it was read from.
+///
+/// ```
+/// use radiotap::{field::FieldValue, OwnedField, RadiotapIterator};
+///
+/// let capture = vec![0, 0, 9, 0, 2, 0, 0, 0, 16];
+/// let fields = RadiotapIterator::from_bytes(&capture)
+///     .unwrap()
+///     .into_iter()
+///     .map(|field| field.map(OwnedField::from))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// drop(capture);
+///
+/// match fields[0].decode().unwrap() {
+///     Some(FieldValue::Flags(flags)) => assert!(flags.fcs),
+///     value => panic!("unexpected value: {:?}", value),
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedField {
+    /// The kind of the field.
+    pub kind: Kind,
+    /// The data of the field.
+    pub data: Vec<u8>,
+}
+
+impl OwnedField {
+    /// Returns the parsed value of the field, or `None` for vendor namespaces
+    /// and TLV fields the crate does not know.
+    pub fn decode(&self) -> Result<Option<FieldValue>> {
+        let mut radiotap = Radiotap::default();
+        radiotap.update(self.kind, &self.data)?;
+        Ok(radiotap.field(self.kind))
+    }
+}
+
+impl<'a> From<(Kind, &'a [u8])> for OwnedField {
+    fn from((kind, data): (Kind, &'a [u8])) -> OwnedField {
+        OwnedField {
+            kind,
+            data: data.to_vec(),
         }
     }
 }
@@ -197,15 +685,59 @@ impl Default for Header {
         Header {
             version: 0,
             length: 8,
-            present: Vec::new(),
+            present: Present::new(),
             size: 8,
+            tlv: false,
+            skipped: Vec::new(),
+            resets: Vec::new(),
         }
     }
 }
 
+/// The present fields of a capture that were decoded and skipped, see
+/// [Radiotap::coverage](struct.Radiotap.html#method.coverage).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coverage {
+    /// The number of present fields in the radiotap namespaces that were
+    /// decoded.
+    pub understood: usize,
+    /// The bits of the present fields in the radiotap namespaces that were
+    /// skipped, because the crate does not parse them or a field the crate
+    /// does not know came before them.
+    pub skipped: Vec<u8>,
+}
+
+/// What to do with a vendor namespace that has no registered
+/// [Namespace](ns/trait.Namespace.html), see
+/// [ParseOptions](struct.ParseOptions.html).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnknownPolicy {
+    /// Skip the vendor namespace, the default.
+    #[default]
+    Skip,
+    /// Return an `UnsupportedField` error.
+    Error,
+    /// Skip the vendor namespace and return its OUI and data.
+    Collect,
+}
+
+/// Options for [Radiotap::parse_with_options](struct.Radiotap.html#method.parse_with_options).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseOptions {
+    /// What to do with unknown vendor namespaces.
+    pub on_unknown_vendor: UnknownPolicy,
+    /// Whether to parse a second Radiotap header at the start of the payload,
+    /// a workaround for drivers that prepend the header twice.
+    pub tolerate_nested: bool,
+}
+
 /// Represents a parsed Radiotap capture, including the parsed header and all
 /// fields as Option members.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Radiotap {
     pub header: Header,
     pub tsft: Option<TSFT>,
@@ -231,6 +763,15 @@ pub struct Radiotap {
     pub ampdu_status: Option<AMPDUStatus>,
     pub vht: Option<VHT>,
     pub timestamp: Option<Timestamp>,
+    pub he: Option<HE>,
+    pub he_mu: Option<HEMU>,
+    pub zero_length_psdu: Option<ZeroLengthPSDU>,
+    pub lsig: Option<LSIG>,
+    pub usig: Option<USIG>,
+    pub eht: Option<EHT>,
+    /// The signal of each antenna, see
+    /// [per_antenna_signals](#method.per_antenna_signals).
+    pub antenna_signals: Vec<(u8, i8)>,
 }
 
 impl Radiotap {
@@ -240,117 +781,3343 @@ impl Radiotap {
         Ok(Radiotap::parse(input)?.0)
     }
 
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) and the 802.11
+    /// frame following it from an input byte array.
+    ///
+    /// The frame starts exactly at the length specified in the Radiotap
+    /// header, `header.length`. This is the same as [parse](#method.parse).
+    pub fn split(input: &[u8]) -> Result<(Radiotap, &[u8])> {
+        Radiotap::parse(input)
+    }
+
     /// Returns the parsed [Radiotap](struct.Radiotap.html) and remaining data
-    /// from an input byte array.
+    /// from an input byte array. The remaining data is everything after the
+    /// length specified in the Radiotap header, usually the 802.11 frame.
     pub fn parse(input: &[u8]) -> Result<(Radiotap, &[u8])> {
+        let mut radiotap = Radiotap::default();
+        let rest = radiotap.decode_into(input)?;
+        Ok((radiotap, rest))
+    }
+
+    /// Parses the input byte array into this
+    /// [Radiotap](struct.Radiotap.html), replacing all of its fields, and
+    /// returns the remaining data like [parse](#method.parse).
+    ///
+    /// This reuses the allocations of a single Radiotap when decoding many
+    /// captures. If an error is returned the fields are left partially
+    /// decoded.
+    ///
+    /// ```
+    /// use radiotap::{field::Rate, Radiotap, RadiotapBuilder};
+    ///
+    /// let mut radiotap = Radiotap::default();
+    /// for rate in [1.0, 2.0].iter() {
+    ///     let capture = RadiotapBuilder::new().rate(Rate { value: *rate }).build();
+    ///     radiotap.decode_into(&capture).unwrap();
+    ///     assert_eq!(radiotap.rate, Some(Rate { value: *rate }));
+    /// }
+    /// ```
+    pub fn decode_into<'a>(&mut self, input: &'a [u8]) -> Result<&'a [u8]> {
+        self.decode_with(input, &ParseOptions::default(), &mut Vec::new())
+    }
+
+    /// Returns the parsed [Radiotap](struct.Radiotap.html), the vendor
+    /// namespaces collected by the options and the remaining data, like
+    /// [parse](#method.parse).
+    ///
+    /// Vendor namespaces are unknown as no
+    /// [Namespace](ns/trait.Namespace.html) is registered, the collected
+    /// namespaces are the OUI and data of each one after its header.
+    ///
+    /// With `tolerate_nested` set, if the payload starts with what
+    /// [detect_header](fn.detect_header.html) takes to be a Radiotap header,
+    /// the inner capture is parsed and returned instead. If the inner capture
+    /// fails to parse, the payload is assumed to be an 802.11 frame and the
+    /// outer capture is returned.
+    ///
+    /// ```
+    /// use radiotap::{Error, ParseOptions, Radiotap, UnknownPolicy};
+    ///
+    /// // Flags and a vendor namespace with 2 bytes of data
+    /// let capture = [0, 0, 22, 0, 2, 0, 0, 192, 0, 0, 0, 0, 16, 0, 0, 17, 34, 0, 2, 0, 1, 2];
+    /// let options = ParseOptions {
+    ///     on_unknown_vendor: UnknownPolicy::Error,
+    ///     ..ParseOptions::default()
+    /// };
+    /// match Radiotap::parse_with_options(&capture, &options) {
+    ///     Err(Error::UnsupportedField) => {}
+    ///     result => panic!("unexpected result: {:?}", result),
+    /// }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn parse_with_options<'a>(
+        input: &'a [u8],
+        options: &ParseOptions,
+    ) -> Result<(Radiotap, Vec<(Oui, &'a [u8])>, &'a [u8])> {
+        let mut radiotap = Radiotap::default();
+        let mut vendor = Vec::new();
+        let rest = radiotap.decode_with(input, options, &mut vendor)?;
+
+        if options.tolerate_nested && detect_header(rest) == HeaderKind::Radiotap {
+            let mut inner = Radiotap::default();
+            let mut inner_vendor = Vec::new();
+            if let Ok(inner_rest) = inner.decode_with(rest, options, &mut inner_vendor) {
+                return Ok((inner, inner_vendor, inner_rest));
+            }
+        }
+
+        Ok((radiotap, vendor, rest))
+    }
+
+    /// Parses the input like [decode_into](#method.decode_into), handling
+    /// unknown vendor namespaces by the options.
+    fn decode_with<'a>(
+        &mut self,
+        input: &'a [u8],
+        options: &ParseOptions,
+        vendor: &mut Vec<(Oui, &'a [u8])>,
+    ) -> Result<&'a [u8]> {
         let (iterator, rest) = RadiotapIterator::parse(input)?;
 
-        let mut radiotap = Radiotap {
+        let mut antenna_signals = mem::take(&mut self.antenna_signals);
+        antenna_signals.clear();
+        *self = Radiotap {
             header: iterator.header.clone(),
+            antenna_signals,
             ..Default::default()
         };
 
-        for result in &iterator {
-            let (field_kind, data) = result?;
-
-            match field_kind {
-                Kind::TSFT => radiotap.tsft = from_bytes_some(data)?,
-                Kind::Flags => radiotap.flags = from_bytes_some(data)?,
-                Kind::Rate => radiotap.rate = from_bytes_some(data)?,
-                Kind::Channel => radiotap.channel = from_bytes_some(data)?,
-                Kind::FHSS => radiotap.fhss = from_bytes_some(data)?,
-                Kind::AntennaSignal => radiotap.antenna_signal = from_bytes_some(data)?,
-                Kind::AntennaNoise => radiotap.antenna_noise = from_bytes_some(data)?,
-                Kind::LockQuality => radiotap.lock_quality = from_bytes_some(data)?,
-                Kind::TxAttenuation => radiotap.tx_attenuation = from_bytes_some(data)?,
-                Kind::TxAttenuationDb => radiotap.tx_attenuation_db = from_bytes_some(data)?,
-                Kind::TxPower => radiotap.tx_power = from_bytes_some(data)?,
-                Kind::Antenna => radiotap.antenna = from_bytes_some(data)?,
-                Kind::AntennaSignalDb => radiotap.antenna_signal_db = from_bytes_some(data)?,
-                Kind::AntennaNoiseDb => radiotap.antenna_noise_db = from_bytes_some(data)?,
-                Kind::RxFlags => radiotap.rx_flags = from_bytes_some(data)?,
-                Kind::TxFlags => radiotap.tx_flags = from_bytes_some(data)?,
-                Kind::RTSRetries => radiotap.rts_retries = from_bytes_some(data)?,
-                Kind::DataRetries => radiotap.data_retries = from_bytes_some(data)?,
-                Kind::XChannel => radiotap.xchannel = from_bytes_some(data)?,
-                Kind::MCS => radiotap.mcs = from_bytes_some(data)?,
-                Kind::AMPDUStatus => radiotap.ampdu_status = from_bytes_some(data)?,
-                Kind::VHT => radiotap.vht = from_bytes_some(data)?,
-                Kind::Timestamp => radiotap.timestamp = from_bytes_some(data)?,
-                _ => {}
+        let mut chain = AntennaChain::default();
+        let mut fields = iterator.into_iter();
+        while let Some(result) = fields.next() {
+            let (kind, data) = result?;
+            if let Kind::VendorNamespace(Some(vns)) = kind {
+                match options.on_unknown_vendor {
+                    UnknownPolicy::Skip => {}
+                    UnknownPolicy::Error => return Err(Error::UnsupportedField),
+                    UnknownPolicy::Collect => vendor.push((vns.oui, data)),
+                }
             }
+            let start = fields.cursor.position() - data.len();
+            self.update_chain(&mut chain, fields.namespace(), kind, data)
+                .map_err(|err| err.context(start, kind))?;
         }
 
-        Ok((radiotap, rest))
+        Ok(rest)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) read from the
+    /// reader, along with the raw capture it was parsed from.
+    ///
+    /// Only the length specified in the Radiotap header is read, the reader is
+    /// left at the start of the data after it, usually the 802.11 frame. If
+    /// the reader ends before then an `IncompleteError` is returned with the
+    /// number of bytes read.
+    ///
+    /// As no payload is read, the raw capture is returned in its place, to log
+    /// or forward the header without encoding it again.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(reader: &mut R) -> Result<(Radiotap, Vec<u8>)> {
+        let mut capture = vec![0; 4];
+        read_full(reader, &mut capture, 0)?;
 
-    #[test]
-    fn good_vendor() {
-        let frame = [
-            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+        let length = usize::from(u16::from_le_bytes([capture[2], capture[3]]));
+        if length > capture.len() {
+            capture.resize(length, 0);
+            read_full(reader, &mut capture[4..], 4)?;
+        }
 
-        assert_eq!(
-            Radiotap::from_bytes(&frame).unwrap().rate.unwrap(),
-            Rate { value: 2.0 }
-        );
+        Ok((Radiotap::from_bytes(&capture)?, capture))
     }
 
-    #[test]
-    fn bad_version() {
-        let frame = [
-            1, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) from an input byte
+    /// array, along with the raw bytes of each field, to compare the decoded
+    /// values against the capture.
+    ///
+    /// If a kind of field is present in several radiotap namespaces the bytes
+    /// of the last one are kept, as in the parsed fields.
+    #[cfg(feature = "std")]
+    pub fn parse_with_raw(
+        input: &[u8],
+    ) -> Result<(Radiotap, std::collections::HashMap<Kind, Vec<u8>>)> {
+        let radiotap = Radiotap::from_bytes(input)?;
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::UnsupportedVersion => {}
-            e => panic!("Error not UnsupportedVersion: {:?}", e),
-        };
+        let mut raw = std::collections::HashMap::new();
+        for result in &RadiotapIterator::from_bytes(input)? {
+            let (kind, data) = result?;
+            raw.insert(kind, data.to_vec());
+        }
+
+        Ok((radiotap, raw))
     }
 
-    #[test]
-    fn bad_header_length() {
-        let frame = [
-            0, 0, 40, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) from an input byte
+    /// array, along with the errors of any fields that could not be parsed.
+    ///
+    /// Unlike [from_bytes](#method.from_bytes) a field that fails to parse
+    /// does not discard the whole capture, the error is recorded with the kind
+    /// of the field and parsing continues with the next field. If a field can
+    /// not be located then the fields after it are skipped. Errors in the
+    /// header are still returned.
+    pub fn from_bytes_lenient(input: &[u8]) -> Result<(Radiotap, Vec<(Kind, Error)>)> {
+        let iterator = RadiotapIterator::from_bytes(input)?;
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::InvalidLength => {}
-            e => panic!("Error not InvalidLength: {:?}", e),
+        let mut radiotap = Radiotap {
+            header: iterator.header.clone(),
+            ..Default::default()
         };
+        let mut errors = Vec::new();
+
+        let mut chain = AntennaChain::default();
+        let mut fields = iterator.into_iter();
+        while let Some(result) = fields.next() {
+            match result {
+                Ok((kind, data)) => {
+                    let start = fields.cursor.position() - data.len();
+                    if let Err(err) =
+                        radiotap.update_chain(&mut chain, fields.namespace(), kind, data)
+                    {
+                        errors.push((kind, err.context(start, kind)));
+                    }
+                }
+                Err(err) => {
+                    match err {
+                        Error::IncompleteError {
+                            kind: Some(kind), ..
+                        } => errors.push((kind, err)),
+                        err => return Err(err),
+                    }
+                    // The fields after this one can't be located
+                    break;
+                }
+            }
+        }
+
+        Ok((radiotap, errors))
     }
 
-    #[test]
-    fn bad_actual_length() {
-        let frame = [
-            0, 0, 39, 0, 47, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+    /// Returns a table of the fields in the capture, with the present bit,
+    /// kind, alignment, offset and length of each field and its data in hex.
+    ///
+    /// This is meant for debugging captures that misparse, the fields are not
+    /// decoded.
+    ///
+    /// ```
+    /// use radiotap::{field::Rate, Radiotap, RadiotapBuilder};
+    ///
+    /// let capture = RadiotapBuilder::new().rate(Rate { value: 6.0 }).build();
+    /// println!("{}", Radiotap::debug_dump(&capture).unwrap());
+    /// ```
+    pub fn debug_dump(input: &[u8]) -> Result<String> {
+        let iterator = RadiotapIterator::from_bytes(input)?;
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::IncompleteError => {}
-            e => panic!("Error not IncompleteError: {:?}", e),
-        };
+        let mut dump = format!(
+            "{:>3} {:<17} {:>5} {:>6} {:>4} data\n",
+            "bit", "kind", "align", "offset", "len"
+        );
+        let mut fields = iterator.into_iter();
+        while let Some(result) = fields.next() {
+            let (kind, data) = result?;
+            let start = fields.cursor.position() - data.len();
+            let hex: Vec<_> = data.iter().map(|byte| format!("{:02x}", byte)).collect();
+            dump.push_str(&format!(
+                "{:>3} {:<17} {:>5} {:>6} {:>4} {}\n",
+                kind.bit(),
+                kind.name(),
+                kind.align(),
+                start,
+                data.len(),
+                hex.join(" ")
+            ));
+        }
+
+        Ok(dump)
     }
 
-    #[test]
-    fn bad_vendor() {
-        let frame = [
-            0, 0, 34, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255,
-        ];
+    /// Returns the value of the field of the given kind, or `None` if it is not
+    /// present. Vendor namespaces and unknown TLV fields are not stored so are
+    /// always `None`.
+    ///
+    /// ```
+    /// use radiotap::{field::{FieldValue, Kind, Rate}, Radiotap, RadiotapBuilder};
+    ///
+    /// let capture = RadiotapBuilder::new().rate(Rate { value: 6.0 }).build();
+    /// let radiotap = Radiotap::from_bytes(&capture).unwrap();
+    /// for &kind in radiotap.header.present.iter() {
+    ///     assert_eq!(radiotap.field(kind), Some(FieldValue::Rate(Rate { value: 6.0 })));
+    /// }
+    /// ```
+    pub fn field(&self, kind: Kind) -> Option<FieldValue> {
+        match kind {
+            Kind::TSFT => self.tsft.map(FieldValue::TSFT),
+            Kind::Flags => self.flags.map(FieldValue::Flags),
+            Kind::Rate => self.rate.map(FieldValue::Rate),
+            Kind::Channel => self.channel.map(FieldValue::Channel),
+            Kind::FHSS => self.fhss.map(FieldValue::FHSS),
+            Kind::AntennaSignal => self.antenna_signal.map(FieldValue::AntennaSignal),
+            Kind::AntennaNoise => self.antenna_noise.map(FieldValue::AntennaNoise),
+            Kind::LockQuality => self.lock_quality.map(FieldValue::LockQuality),
+            Kind::TxAttenuation => self.tx_attenuation.map(FieldValue::TxAttenuation),
+            Kind::TxAttenuationDb => self.tx_attenuation_db.map(FieldValue::TxAttenuationDb),
+            Kind::TxPower => self.tx_power.map(FieldValue::TxPower),
+            Kind::Antenna => self.antenna.map(FieldValue::Antenna),
+            Kind::AntennaSignalDb => self.antenna_signal_db.map(FieldValue::AntennaSignalDb),
+            Kind::AntennaNoiseDb => self.antenna_noise_db.map(FieldValue::AntennaNoiseDb),
+            Kind::RxFlags => self.rx_flags.map(FieldValue::RxFlags),
+            Kind::TxFlags => self.tx_flags.map(FieldValue::TxFlags),
+            Kind::RTSRetries => self.rts_retries.map(FieldValue::RTSRetries),
+            Kind::DataRetries => self.data_retries.map(FieldValue::DataRetries),
+            Kind::XChannel => self.xchannel.map(FieldValue::XChannel),
+            Kind::MCS => self.mcs.map(FieldValue::MCS),
+            Kind::AMPDUStatus => self.ampdu_status.map(FieldValue::AMPDUStatus),
+            Kind::VHT => self.vht.map(FieldValue::VHT),
+            Kind::Timestamp => self.timestamp.map(FieldValue::Timestamp),
+            Kind::HE => self.he.map(FieldValue::HE),
+            Kind::HEMU => self.he_mu.map(FieldValue::HEMU),
+            Kind::ZeroLengthPSDU => self.zero_length_psdu.map(FieldValue::ZeroLengthPSDU),
+            Kind::LSIG => self.lsig.map(FieldValue::LSIG),
+            Kind::USIG => self.usig.map(FieldValue::USIG),
+            Kind::EHT => self.eht.clone().map(FieldValue::EHT),
+            Kind::VendorNamespace(_) | Kind::VendorField(_) | Kind::TLV(_) | Kind::Unparsed(_) => {
+                None
+            }
+        }
+    }
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::IncompleteError => {}
-            e => panic!("Error not IncompleteError: {:?}", e),
+    /// Returns whether the frame was received intact.
+    ///
+    /// Returns `Some(false)` if the [Flags](field/struct.Flags.html) report a
+    /// bad FCS or the [RxFlags](field/struct.RxFlags.html) report a bad PLCP,
+    /// `Some(true)` if the frame has an FCS that is not bad, and `None` if
+    /// there is no integrity information.
+    pub fn frame_ok(&self) -> Option<bool> {
+        let bad_fcs = matches!(self.flags, Some(Flags { bad_fcs: true, .. }));
+        let bad_plcp = matches!(self.rx_flags, Some(RxFlags { bad_plcp: true }));
+        if bad_fcs || bad_plcp {
+            return Some(false);
+        }
+
+        match self.flags {
+            Some(flags) if flags.fcs => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Returns the channel from the [XChannel](field/struct.XChannel.html)
+    /// field if present, otherwise from the
+    /// [Channel](field/struct.Channel.html) field.
+    pub fn channel_info(&self) -> Option<ChannelInfo> {
+        self.xchannel
+            .map(ChannelInfo::from)
+            .or_else(|| self.channel.map(ChannelInfo::from))
+    }
+
+    /// Returns the bandwidth of the frame in MHz, from the
+    /// [U-SIG](field/struct.USIG.html), [HE](field/struct.HE.html),
+    /// [VHT](field/struct.VHT.html), or [MCS](field/struct.MCS.html) field,
+    /// whichever is present first in that order.
+    pub fn bandwidth_mhz(&self) -> Option<u16> {
+        self.usig
+            .and_then(|usig| usig.bw.map(|bw| bw.mhz()))
+            .or_else(|| self.he.and_then(|he| he.bw.map(|bw| bw.mhz())))
+            .or_else(|| self.vht.and_then(|vht| vht.bw.map(|bw| bw.mhz())))
+            .or_else(|| self.mcs.and_then(|mcs| mcs.bw.map(|bw| bw.mhz())))
+    }
+
+    /// Returns the field describing the PHY that transmitted the frame.
+    ///
+    /// Drivers may report a legacy [Rate](field/struct.Rate.html) alongside
+    /// the field of a newer PHY, so the newest PHY present wins, in the order
+    /// EHT ([U-SIG](field/struct.USIG.html) or [EHT](field/struct.EHT.html)),
+    /// [HE](field/struct.HE.html), [VHT](field/struct.VHT.html),
+    /// [MCS](field/struct.MCS.html), and finally the legacy Rate. Returns
+    /// `None` if none of these is present.
+    pub fn primary_phy(&self) -> Option<PhyField> {
+        if self.eht.is_some() || self.usig.is_some() {
+            Some(PhyField::EHT)
+        } else if self.he.is_some() {
+            Some(PhyField::HE)
+        } else if self.vht.is_some() {
+            Some(PhyField::VHT)
+        } else if self.mcs.is_some() {
+            Some(PhyField::MCS)
+        } else if self.rate.is_some() {
+            Some(PhyField::Legacy)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the PHY that transmitted the frame.
+    ///
+    /// The PHY is that of the [primary_phy](#method.primary_phy) field. For
+    /// legacy frames the [Rate](field/struct.Rate.html) distinguishes DSSS,
+    /// CCK and OFDM. Without any of these fields the CCK and OFDM flags of the
+    /// [XChannel](field/struct.XChannel.html) or
+    /// [Channel](field/struct.Channel.html) are used. Returns `None` if none
+    /// of these is known.
+    pub fn phy_mode(&self) -> Option<PhyMode> {
+        match self.primary_phy() {
+            Some(PhyField::EHT) => return Some(PhyMode::EHT),
+            Some(PhyField::HE) => return Some(PhyMode::HE),
+            Some(PhyField::VHT) => return Some(PhyMode::VHT),
+            Some(PhyField::MCS) => return Some(PhyMode::HT),
+            Some(PhyField::Legacy) | None => {}
+        }
+
+        if let Some(rate) = self.rate {
+            // The rate in units of 500 Kbps
+            return Some(match (rate.value * 2.0) as u32 {
+                2 | 4 => PhyMode::DSSS,
+                11 | 22 | 44 | 66 => PhyMode::CCK,
+                _ => PhyMode::OFDM,
+            });
+        }
+
+        let (ofdm, cck) = match (self.xchannel, self.channel) {
+            (Some(xchannel), _) => (xchannel.flags.ofdm, xchannel.flags.cck),
+            (None, Some(channel)) => (channel.flags.ofdm, channel.flags.cck),
+            (None, None) => return None,
         };
+        if ofdm {
+            Some(PhyMode::OFDM)
+        } else if cck {
+            Some(PhyMode::CCK)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the data rate of the frame in Mbps.
+    ///
+    /// The rate is that of the [primary_phy](#method.primary_phy) field: the
+    /// highest datarate of the [VHT](field/struct.VHT.html) users, the
+    /// [MCS](field/struct.MCS.html) datarate, or the legacy
+    /// [Rate](field/struct.Rate.html). Returns `None` if the datarate of the
+    /// primary field is not known, without falling back to the other fields,
+    /// and for HE and EHT frames.
+    pub fn data_rate_mbps(&self) -> Option<f32> {
+        match self.primary_phy()? {
+            PhyField::VHT => self
+                .vht?
+                .users
+                .iter()
+                .flatten()
+                .filter_map(|user| user.datarate)
+                .fold(None, |max: Option<f32>, datarate| {
+                    Some(max.map_or(datarate, |max| max.max(datarate)))
+                }),
+            PhyField::MCS => self.mcs?.datarate,
+            PhyField::Legacy => self.rate.map(|rate| rate.value),
+            PhyField::HE | PhyField::EHT => None,
+        }
+    }
+
+    /// Returns the estimated airtime in microseconds of a frame with the given
+    /// length in bytes, including the preamble.
+    ///
+    /// DSSS and CCK frames use the long or short preamble given by the
+    /// [Flags](field/struct.Flags.html). OFDM frames use the legacy
+    /// [Rate](field/struct.Rate.html), HT frames the
+    /// [MCS](field/struct.MCS.html) field, and VHT frames the first user of
+    /// the [VHT](field/struct.VHT.html) field. The OFDM signal extension in
+    /// 2.4 GHz is not included. Returns `None` for HE and EHT frames, or if
+    /// the PHY or its data rate is not known.
+    pub fn airtime_us(&self, payload_len: usize) -> Option<f32> {
+        let bits = 8 * payload_len as u64;
+
+        match self.phy_mode()? {
+            PhyMode::DSSS | PhyMode::CCK => {
+                // The rate in units of 500 Kbps
+                let rate = u64::from(self.rate?.raw());
+                let preamble = match self.flags {
+                    Some(Flags { preamble: true, .. }) => 96.0,
+                    _ => 192.0,
+                };
+                Some(preamble + div_ceil(2 * bits, rate)? as f32)
+            }
+            PhyMode::OFDM => {
+                let rate = self.rate?.value;
+                // The legacy preamble and SIGNAL
+                Some(20.0 + ofdm_data_us(bits, rate, None)?)
+            }
+            PhyMode::HT => {
+                let mcs = self.mcs?;
+                let nsts = ht_streams(mcs.index?) + mcs.stbc.unwrap_or(0);
+                // The legacy preamble, HT-SIG, HT-STF and HT-LTFs
+                let preamble = 32.0 + 4.0 * training_fields(nsts);
+                Some(preamble + ofdm_data_us(bits, mcs.datarate?, mcs.gi)?)
+            }
+            PhyMode::VHT => {
+                let vht = self.vht?;
+                let user = vht.users.iter().flatten().next()?;
+                // The legacy preamble, VHT-SIG-A, VHT-STF, VHT-LTFs and
+                // VHT-SIG-B
+                let preamble = 36.0 + 4.0 * training_fields(user.nsts);
+                Some(preamble + ofdm_data_us(bits, user.datarate?, vht.gi)?)
+            }
+            PhyMode::HE | PhyMode::EHT => None,
+        }
+    }
+
+    /// Returns how many of the present fields were decoded and which were
+    /// skipped, to detect fields that are silently ignored. Vendor namespaces
+    /// and TLV fields are not counted.
+    pub fn coverage(&self) -> Coverage {
+        let mut coverage = Coverage::default();
+        for kind in self.header.present.iter() {
+            match kind {
+                Kind::VendorNamespace(_) | Kind::VendorField(_) => {}
+                Kind::Unparsed(bit) => coverage.skipped.push(*bit),
+                _ => coverage.understood += 1,
+            }
+        }
+        coverage.skipped.extend_from_slice(&self.header.skipped);
+        coverage
+    }
+
+    /// Returns whether the captures are equal, with the datarates of the
+    /// [Rate](field/struct.Rate.html), [MCS](field/struct.MCS.html), and
+    /// [VHT](field/struct.VHT.html) fields compared within the given tolerance
+    /// in Mbps.
+    ///
+    /// Unlike `==` this does not depend on the exact values of the rate
+    /// tables.
+    pub fn approx_eq(&self, other: &Radiotap, epsilon: f32) -> bool {
+        fn option_eq<T>(a: Option<T>, b: Option<T>, eq: impl Fn(&T, &T) -> bool) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => eq(&a, &b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
+        let mut a = self.clone();
+        let mut b = other.clone();
+        option_eq(a.rate.take(), b.rate.take(), |a, b| a.approx_eq(b, epsilon))
+            && option_eq(a.mcs.take(), b.mcs.take(), |a, b| a.approx_eq(b, epsilon))
+            && option_eq(a.vht.take(), b.vht.take(), |a, b| a.approx_eq(b, epsilon))
+            && a == b
+    }
+
+    /// Returns the time of the frame in microseconds by the MAC clock, from the
+    /// [TSFT](field/struct.TSFT.html) or, if it is absent, the
+    /// [Timestamp](field/struct.Timestamp.html).
+    ///
+    /// This is the time of the capturing device's clock, not wall clock time,
+    /// and the two fields may not use the same clock.
+    pub fn mac_time(&self) -> Option<MacTime> {
+        match (self.tsft, self.timestamp) {
+            (Some(tsft), _) => Some(MacTime::TSFT(tsft.value)),
+            (None, Some(timestamp)) => Some(MacTime::Timestamp(timestamp.micros())),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the [AntennaSignal](field/struct.AntennaSignal.html) as a
+    /// quality from 0 to 100 percent, see
+    /// [quality_percent](field/struct.AntennaSignal.html#method.quality_percent).
+    pub fn signal_quality(&self) -> Option<u8> {
+        self.antenna_signal.map(|signal| signal.quality_percent())
+    }
+
+    /// Returns the combined signal in dBm of all antennas, the power sum of the
+    /// [per_antenna_signals](#method.per_antenna_signals). With a single
+    /// antenna its signal is returned, and without per antenna signals the
+    /// [AntennaSignal](field/struct.AntennaSignal.html) is returned.
+    ///
+    /// Requires the `std` feature for the floating point functions.
+    #[cfg(feature = "std")]
+    pub fn combined_rssi_dbm(&self) -> Option<i8> {
+        match self.antenna_signals[..] {
+            [] => self.antenna_signal.map(|signal| signal.value),
+            [(_, signal)] => Some(signal),
+            ref signals => {
+                let mw: f32 = signals
+                    .iter()
+                    .map(|&(_, signal)| 10f32.powf(f32::from(signal) / 10.0))
+                    .sum();
+                // Casting saturates at the bounds of i8
+                Some((10.0 * mw.log10()).round() as i8)
+            }
+        }
+    }
+
+    /// Returns the antenna index and the signal in dBm of each antenna that
+    /// reported a signal.
+    ///
+    /// Captures from multiple radio chains repeat the
+    /// [AntennaSignal](field/struct.AntennaSignal.html) and
+    /// [Antenna](field/struct.Antenna.html) fields in extra radiotap
+    /// namespaces, one per antenna. Each signal is paired with the antenna
+    /// index that follows it. The `antenna_signal` and `antenna` fields only
+    /// keep the last of these. These are not written by
+    /// [to_bytes](#method.to_bytes).
+    pub fn per_antenna_signals(&self) -> &[(u8, i8)] {
+        &self.antenna_signals
+    }
+
+    /// Returns the present fields as key and value pairs, for structured
+    /// logging. Absent fields and unknown sub-fields are omitted.
+    ///
+    /// Units are part of the key, e.g. `antenna_signal_dbm`, and enumerations
+    /// are written as their variant names. Flags are written as a comma
+    /// separated list of the set flags. Only the first VHT user and the first
+    /// EHT user are included.
+    ///
+    /// ```
+    /// use radiotap::Radiotap;
+    ///
+    /// let radiotap = Radiotap::from_bytes(&[0, 0, 9, 0, 32, 0, 0, 0, 213]).unwrap();
+    /// assert_eq!(radiotap.to_kv(), [("antenna_signal_dbm", "-43".to_string())]);
+    /// ```
+    pub fn to_kv(&self) -> Vec<(&'static str, String)> {
+        let mut kv = Vec::new();
+        let mut push = |key: &'static str, value: String| kv.push((key, value));
+
+        if let Some(tsft) = self.tsft {
+            push("tsft_us", tsft.value.to_string());
+        }
+        if let Some(flags) = self.flags {
+            push(
+                "flags",
+                set_flags(&[
+                    ("cfp", flags.cfp),
+                    ("short_preamble", flags.preamble),
+                    ("wep", flags.wep),
+                    ("fragmentation", flags.fragmentation),
+                    ("fcs", flags.fcs),
+                    ("data_pad", flags.data_pad),
+                    ("bad_fcs", flags.bad_fcs),
+                    ("short_gi", flags.sgi),
+                ]),
+            );
+        }
+        if let Some(rate) = self.rate {
+            push("rate_mbps", format!("{:.1}", rate.value));
+        }
+        if let Some(channel) = self.channel {
+            push("channel_freq", channel.freq.to_string());
+            if let Some(number) = channel.channel_number() {
+                push("channel_number", number.to_string());
+            }
+        }
+        if let Some(fhss) = self.fhss {
+            push("fhss_hopset", fhss.hopset.to_string());
+            push("fhss_pattern", fhss.pattern.to_string());
+        }
+        if let Some(antenna_signal) = self.antenna_signal {
+            push("antenna_signal_dbm", antenna_signal.value.to_string());
+        }
+        if let Some(antenna_noise) = self.antenna_noise {
+            push("antenna_noise_dbm", antenna_noise.value.to_string());
+        }
+        if let Some(lock_quality) = self.lock_quality {
+            push("lock_quality", lock_quality.value.to_string());
+        }
+        if let Some(tx_attenuation) = self.tx_attenuation {
+            push("tx_attenuation", tx_attenuation.value.to_string());
+        }
+        if let Some(tx_attenuation_db) = self.tx_attenuation_db {
+            push("tx_attenuation_db", tx_attenuation_db.value.to_string());
+        }
+        if let Some(tx_power) = self.tx_power {
+            push("tx_power_dbm", tx_power.value.to_string());
+        }
+        if let Some(antenna) = self.antenna {
+            push("antenna", antenna.value.to_string());
+        }
+        if let Some(antenna_signal_db) = self.antenna_signal_db {
+            push("antenna_signal_db", antenna_signal_db.value.to_string());
+        }
+        if let Some(antenna_noise_db) = self.antenna_noise_db {
+            push("antenna_noise_db", antenna_noise_db.value.to_string());
+        }
+        if let Some(rx_flags) = self.rx_flags {
+            push("rx_flags", set_flags(&[("bad_plcp", rx_flags.bad_plcp)]));
+        }
+        if let Some(tx_flags) = self.tx_flags {
+            push(
+                "tx_flags",
+                set_flags(&[
+                    ("fail", tx_flags.fail),
+                    ("cts", tx_flags.cts),
+                    ("rts", tx_flags.rts),
+                    ("no_ack", tx_flags.no_ack),
+                    ("no_seq", tx_flags.no_seq),
+                    ("order", tx_flags.order),
+                ]),
+            );
+        }
+        if let Some(rts_retries) = self.rts_retries {
+            push("rts_retries", rts_retries.value.to_string());
+        }
+        if let Some(data_retries) = self.data_retries {
+            push("data_retries", data_retries.value.to_string());
+        }
+        if let Some(xchannel) = self.xchannel {
+            push("xchannel_freq", xchannel.freq.to_string());
+            push("xchannel_number", xchannel.channel.to_string());
+            push("xchannel_max_power_dbm", xchannel.max_power.to_string());
+        }
+        if let Some(mcs) = self.mcs {
+            if let Some(index) = mcs.index {
+                push("mcs_index", index.to_string());
+            }
+            if let Some(bw) = mcs.bw {
+                push("mcs_bandwidth_mhz", bw.bandwidth.to_string());
+            }
+            if let Some(gi) = mcs.gi {
+                push("mcs_gi_ns", gi.nanos().to_string());
+            }
+            if let Some(fec) = mcs.fec {
+                push("mcs_fec", format!("{:?}", fec));
+            }
+            if let Some(stbc) = mcs.stbc {
+                push("mcs_stbc", stbc.to_string());
+            }
+            if let Some(datarate) = mcs.datarate {
+                push("mcs_datarate_mbps", format!("{:.1}", datarate));
+            }
+        }
+        if let Some(ampdu_status) = self.ampdu_status {
+            push("ampdu_reference", ampdu_status.reference.to_string());
+            if let Some(last) = ampdu_status.last {
+                push("ampdu_last", last.to_string());
+            }
+        }
+        if let Some(vht) = self.vht {
+            if let Some(bw) = vht.bw {
+                push("vht_bandwidth_mhz", bw.bandwidth.to_string());
+            }
+            if let Some(gi) = vht.gi {
+                push("vht_gi_ns", gi.nanos().to_string());
+            }
+            if let Some(stbc) = vht.stbc {
+                push("vht_stbc", stbc.to_string());
+            }
+            if let Some(group_id) = vht.group_id {
+                push("vht_group_id", group_id.to_string());
+            }
+            if let Some(partial_aid) = vht.partial_aid {
+                push("vht_partial_aid", partial_aid.to_string());
+            }
+            if let Some(user) = vht.users.iter().flatten().next() {
+                push("vht_mcs_index", user.index.to_string());
+                push("vht_nss", user.nss.to_string());
+                push("vht_fec", format!("{:?}", user.fec));
+                if let Some(datarate) = user.datarate {
+                    push("vht_datarate_mbps", format!("{:.1}", datarate));
+                }
+            }
+        }
+        if let Some(timestamp) = self.timestamp {
+            push("timestamp", timestamp.timestamp.to_string());
+            push("timestamp_unit", format!("{:?}", timestamp.unit));
+            if let Some(accuracy) = timestamp.accuracy {
+                push("timestamp_accuracy", accuracy.to_string());
+            }
+        }
+        if let Some(he) = self.he {
+            push("he_format", format!("{:?}", he.format));
+            if let Some(index) = he.index {
+                push("he_mcs_index", index.to_string());
+            }
+            if let Some(bss_color) = he.bss_color {
+                push("he_bss_color", bss_color.to_string());
+            }
+            if let Some(bw) = he.bw {
+                push("he_bandwidth", format!("{:?}", bw));
+            }
+            if let Some(gi) = he.gi {
+                push("he_gi_ns", gi.nanos().to_string());
+            }
+            if let Some(nsts) = he.nsts {
+                push("he_nsts", nsts.to_string());
+            }
+            if let Some(fec) = he.fec {
+                push("he_fec", format!("{:?}", fec));
+            }
+            if let Some(dcm) = he.dcm {
+                push("he_dcm", dcm.to_string());
+            }
+        }
+        if let Some(he_mu) = self.he_mu {
+            if let Some(index) = he_mu.sig_b_index {
+                push("he_mu_sig_b_mcs_index", index.to_string());
+            }
+            if let Some(symbols) = he_mu.sig_b_symbols {
+                push("he_mu_sig_b_symbols", symbols.to_string());
+            }
+            if let Some(bw) = he_mu.bw {
+                push("he_mu_bandwidth", format!("{:?}", bw));
+            }
+        }
+        if let Some(zero_length_psdu) = self.zero_length_psdu {
+            push(
+                "zero_length_psdu_type",
+                format!("{:?}", zero_length_psdu.psdu_type),
+            );
+        }
+        if let Some(lsig) = self.lsig {
+            if let Some(rate) = lsig.rate {
+                push("lsig_rate", rate.to_string());
+            }
+            if let Some(length) = lsig.length {
+                push("lsig_length", length.to_string());
+            }
+        }
+        if let Some(usig) = self.usig {
+            if let Some(phy_version) = usig.phy_version {
+                push("usig_phy_version", phy_version.to_string());
+            }
+            if let Some(bw) = usig.bw {
+                push("usig_bandwidth", format!("{:?}", bw));
+            }
+            if let Some(bss_color) = usig.bss_color {
+                push("usig_bss_color", bss_color.to_string());
+            }
+            if let Some(txop) = usig.txop {
+                push("usig_txop", txop.to_string());
+            }
+            push("usig_bad_crc", usig.bad_crc.to_string());
+        }
+        if let Some(eht) = &self.eht {
+            if let Some(gi) = eht.gi {
+                push("eht_gi_ns", gi.nanos().to_string());
+            }
+            if let Some(nss) = eht.nss {
+                push("eht_nss", nss.to_string());
+            }
+            if let Some(ru_size) = eht.ru_size {
+                push("eht_ru_size", ru_size.to_string());
+            }
+            if let Some(user) = eht.users.first() {
+                if let Some(index) = user.index {
+                    push("eht_mcs_index", index.to_string());
+                }
+                if let Some(fec) = user.fec {
+                    push("eht_fec", format!("{:?}", fec));
+                }
+            }
+        }
+        if !self.antenna_signals.is_empty() {
+            let signals = self
+                .antenna_signals
+                .iter()
+                .map(|(antenna, signal)| format!("{}:{}", antenna, signal))
+                .collect::<Vec<_>>();
+            push("antenna_signals_dbm", signals.join(","));
+        }
+
+        kv
+    }
+
+    /// Returns the Radiotap capture encoded from the present fields. The header
+    /// is computed from the present fields, vendor namespaces are not included.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let fields = vec![
+            (Kind::TSFT, self.tsft.as_ref().map(Field::to_bytes)),
+            (Kind::Flags, self.flags.as_ref().map(Field::to_bytes)),
+            (Kind::Rate, self.rate.as_ref().map(Field::to_bytes)),
+            (Kind::Channel, self.channel.as_ref().map(Field::to_bytes)),
+            (Kind::FHSS, self.fhss.as_ref().map(Field::to_bytes)),
+            (
+                Kind::AntennaSignal,
+                self.antenna_signal.as_ref().map(Field::to_bytes),
+            ),
+            (
+                Kind::AntennaNoise,
+                self.antenna_noise.as_ref().map(Field::to_bytes),
+            ),
+            (
+                Kind::LockQuality,
+                self.lock_quality.as_ref().map(Field::to_bytes),
+            ),
+            (
+                Kind::TxAttenuation,
+                self.tx_attenuation.as_ref().map(Field::to_bytes),
+            ),
+            (
+                Kind::TxAttenuationDb,
+                self.tx_attenuation_db.as_ref().map(Field::to_bytes),
+            ),
+            (Kind::TxPower, self.tx_power.as_ref().map(Field::to_bytes)),
+            (Kind::Antenna, self.antenna.as_ref().map(Field::to_bytes)),
+            (
+                Kind::AntennaSignalDb,
+                self.antenna_signal_db.as_ref().map(Field::to_bytes),
+            ),
+            (
+                Kind::AntennaNoiseDb,
+                self.antenna_noise_db.as_ref().map(Field::to_bytes),
+            ),
+            (Kind::RxFlags, self.rx_flags.as_ref().map(Field::to_bytes)),
+            (Kind::TxFlags, self.tx_flags.as_ref().map(Field::to_bytes)),
+            (
+                Kind::RTSRetries,
+                self.rts_retries.as_ref().map(Field::to_bytes),
+            ),
+            (
+                Kind::DataRetries,
+                self.data_retries.as_ref().map(Field::to_bytes),
+            ),
+            (Kind::XChannel, self.xchannel.as_ref().map(Field::to_bytes)),
+            (Kind::MCS, self.mcs.as_ref().map(Field::to_bytes)),
+            (
+                Kind::AMPDUStatus,
+                self.ampdu_status.as_ref().map(Field::to_bytes),
+            ),
+            (Kind::VHT, self.vht.as_ref().map(Field::to_bytes)),
+            (
+                Kind::Timestamp,
+                self.timestamp.as_ref().map(Field::to_bytes),
+            ),
+            (Kind::HE, self.he.as_ref().map(Field::to_bytes)),
+            (Kind::HEMU, self.he_mu.as_ref().map(Field::to_bytes)),
+            (
+                Kind::ZeroLengthPSDU,
+                self.zero_length_psdu.as_ref().map(Field::to_bytes),
+            ),
+            (Kind::LSIG, self.lsig.as_ref().map(Field::to_bytes)),
+        ];
+
+        // Fields without a present bit are written as TLV fields
+        let tlvs = vec![
+            (Kind::USIG, self.usig.as_ref().map(Field::to_bytes)),
+            (Kind::EHT, self.eht.as_ref().map(Field::to_bytes)),
+        ];
+
+        let header = Header {
+            present: fields
+                .iter()
+                .filter(|(_, data)| data.is_some())
+                .map(|(kind, _)| *kind)
+                .collect(),
+            tlv: tlvs.iter().any(|(_, data)| data.is_some()),
+            ..Default::default()
+        };
+
+        let mut output = header.to_bytes();
+        for (kind, data) in fields {
+            if let Some(data) = data {
+                let align = kind.align() as usize;
+                output.resize((output.len() + align - 1) & !(align - 1), 0);
+                output.extend_from_slice(&data);
+            }
+        }
+
+        for (kind, data) in tlvs {
+            if let (Some(tlv_type), Some(data)) = (kind.tlv_type(), data) {
+                output.resize((output.len() + 3) & !3, 0);
+                output.extend_from_slice(&tlv_type.to_le_bytes());
+                output.extend_from_slice(&(data.len() as u16).to_le_bytes());
+                output.extend_from_slice(&data);
+            }
+        }
+
+        let length = output.len() as u16;
+        output[2..4].copy_from_slice(&length.to_le_bytes());
+        output
+    }
+
+    /// Updates the field of the given kind by parsing the given data.
+    fn update(&mut self, kind: Kind, data: &[u8]) -> Result<()> {
+        match kind {
+            Kind::TSFT => self.tsft = from_bytes_some(data)?,
+            Kind::Flags => self.flags = from_bytes_some(data)?,
+            Kind::Rate => self.rate = from_bytes_some(data)?,
+            Kind::Channel => self.channel = from_bytes_some(data)?,
+            Kind::FHSS => self.fhss = from_bytes_some(data)?,
+            Kind::AntennaSignal => self.antenna_signal = from_bytes_some(data)?,
+            Kind::AntennaNoise => self.antenna_noise = from_bytes_some(data)?,
+            Kind::LockQuality => self.lock_quality = from_bytes_some(data)?,
+            Kind::TxAttenuation => self.tx_attenuation = from_bytes_some(data)?,
+            Kind::TxAttenuationDb => self.tx_attenuation_db = from_bytes_some(data)?,
+            Kind::TxPower => self.tx_power = from_bytes_some(data)?,
+            Kind::Antenna => self.antenna = from_bytes_some(data)?,
+            Kind::AntennaSignalDb => self.antenna_signal_db = from_bytes_some(data)?,
+            Kind::AntennaNoiseDb => self.antenna_noise_db = from_bytes_some(data)?,
+            Kind::RxFlags => self.rx_flags = from_bytes_some(data)?,
+            Kind::TxFlags => self.tx_flags = from_bytes_some(data)?,
+            Kind::RTSRetries => self.rts_retries = from_bytes_some(data)?,
+            Kind::DataRetries => self.data_retries = from_bytes_some(data)?,
+            Kind::XChannel => self.xchannel = from_bytes_some(data)?,
+            Kind::MCS => self.mcs = from_bytes_some(data)?,
+            Kind::AMPDUStatus => self.ampdu_status = from_bytes_some(data)?,
+            Kind::VHT => self.vht = from_bytes_some(data)?,
+            Kind::Timestamp => self.timestamp = from_bytes_some(data)?,
+            Kind::HE => self.he = from_bytes_some(data)?,
+            Kind::HEMU => self.he_mu = from_bytes_some(data)?,
+            Kind::ZeroLengthPSDU => self.zero_length_psdu = from_bytes_some(data)?,
+            Kind::LSIG => self.lsig = from_bytes_some(data)?,
+            Kind::USIG => self.usig = from_bytes_some(data)?,
+            Kind::EHT => self.eht = from_bytes_some(data)?,
+            // Known fields may also be given as TLV fields, with their present
+            // bit as the TLV type
+            Kind::TLV(tlv_type) if tlv_type < 28 => {
+                if let Ok(kind) = Kind::new(tlv_type as u8) {
+                    self.update(kind, data)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Updates the field of the given kind like [update](#method.update),
+    /// pairing each antenna with the signal before it in the same namespace.
+    fn update_chain(
+        &mut self,
+        chain: &mut AntennaChain,
+        namespace: usize,
+        kind: Kind,
+        data: &[u8],
+    ) -> Result<()> {
+        if namespace != chain.namespace {
+            chain.namespace = namespace;
+            chain.signal = None;
+        }
+
+        self.update(kind, data)?;
+        match kind {
+            Kind::AntennaSignal => chain.signal = self.antenna_signal.map(|signal| signal.value),
+            Kind::Antenna => {
+                if let (Some(antenna), Some(signal)) = (self.antenna, chain.signal) {
+                    self.antenna_signals.push((antenna.value, signal));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// The antenna signal of the namespace being parsed.
+#[derive(Default)]
+struct AntennaChain {
+    /// The index of the current namespace.
+    namespace: usize,
+    /// The antenna signal in the current namespace.
+    signal: Option<i8>,
+}
+
+/// Returns the duration in microseconds of the OFDM symbols carrying the given
+/// number of data bits, along with the service and tail bits, at the given
+/// data rate in Mbps. The guard interval is long unless given as short.
+fn ofdm_data_us(bits: u64, rate: f32, gi: Option<GuardInterval>) -> Option<f32> {
+    let symbol = match gi {
+        Some(GuardInterval::Short) => 3.6,
+        _ => 4.0,
+    };
+    // The data bits per symbol, the rates are rounded to a tenth of a Mbps
+    let bits_per_symbol = (rate * symbol + 0.5) as u64;
+    Some(div_ceil(16 + bits + 6, bits_per_symbol)? as f32 * symbol)
+}
+
+/// Returns the quotient rounded up, or `None` if the divisor is 0.
+fn div_ceil(dividend: u64, divisor: u64) -> Option<u64> {
+    if divisor == 0 {
+        None
+    } else {
+        Some(dividend.div_ceil(divisor))
+    }
+}
+
+/// Returns the number of spatial streams of the 802.11n MCS index.
+fn ht_streams(index: u8) -> u8 {
+    match index {
+        0..=31 => index / 8 + 1,
+        32 => 1,
+        33..=38 => 2,
+        39..=52 => 3,
+        _ => 4,
+    }
+}
+
+/// Returns the number of long training fields for the number of space-time
+/// streams.
+fn training_fields(nsts: u8) -> f32 {
+    match nsts {
+        0..=2 => f32::from(nsts.max(1)),
+        3 | 4 => 4.0,
+        5 | 6 => 6.0,
+        _ => 8.0,
+    }
+}
+
+/// Returns the names of the set flags, separated by commas.
+fn set_flags(flags: &[(&str, bool)]) -> String {
+    flags
+        .iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Formats a summary of the common fields, one per line. Fields that are not
+/// present are omitted.
+impl fmt::Display for Radiotap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut lines = Vec::new();
+        if let Some(tsft) = self.tsft {
+            lines.push(format!("TSFT: {} us", tsft.value));
+        }
+        if let Some(channel) = self.channel {
+            lines.push(format!("Channel: {}", channel));
+        }
+        if let Some(rate) = self.rate {
+            lines.push(format!("Rate: {}", rate));
+        }
+        if let Some(antenna_signal) = self.antenna_signal {
+            lines.push(format!("Signal: {}", antenna_signal));
+        }
+        if let Some(antenna_noise) = self.antenna_noise {
+            lines.push(format!("Noise: {}", antenna_noise));
+        }
+        if let Some(antenna) = self.antenna {
+            lines.push(format!("Antenna: {}", antenna.value));
+        }
+        if let Some(mcs) = self.mcs {
+            lines.push(format!("MCS: {}", mcs));
+        }
+        if let Some(vht) = self.vht {
+            lines.push(format!("VHT: {}", vht));
+        }
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+/// An iterator over the Radiotap frames in a buffer of consecutive frames.
+///
+/// A Radiotap capture doesn't record the length of the payload that follows
+/// it, so each frame is expected to be preceded by its total length, the
+/// Radiotap capture plus the payload, as a little-endian `u32`. Yields the
+/// parsed capture and the payload of each frame.
+///
+/// A frame that fails to parse yields an error and the stream continues with
+/// the next frame. A length that overruns the buffer yields an error and ends
+/// the stream.
+///
+/// ```
+/// use radiotap::{field::Rate, RadiotapBuilder, RadiotapStream};
+///
+/// let mut frame = RadiotapBuilder::new().rate(Rate { value: 6.0 }).build();
+/// frame.extend_from_slice(&[0xd4, 0, 0, 0]);
+///
+/// let mut buffer = (frame.len() as u32).to_le_bytes().to_vec();
+/// buffer.extend_from_slice(&frame);
+///
+/// for result in RadiotapStream::new(&buffer) {
+///     let (radiotap, payload) = result.unwrap();
+///     assert_eq!(radiotap.rate, Some(Rate { value: 6.0 }));
+///     assert_eq!(payload, [0xd4, 0, 0, 0]);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RadiotapStream<'a> {
+    data: &'a [u8],
+    offset: usize,
+    present: Option<Kind>,
+}
+
+impl<'a> RadiotapStream<'a> {
+    /// Returns a stream over the length delimited frames in the buffer.
+    pub fn new(data: &'a [u8]) -> RadiotapStream<'a> {
+        RadiotapStream {
+            data,
+            offset: 0,
+            present: None,
+        }
+    }
+
+    /// Only yield the frames with a field of the given kind present.
+    ///
+    /// The present words in the header of each frame are checked before the
+    /// fields are decoded, other frames are skipped without decoding them. See
+    /// [Header::has_field](field/struct.Header.html#method.has_field) for how
+    /// vendor namespaces are matched. A frame with an invalid header still
+    /// yields an error.
+    pub fn filter_present(mut self, kind: Kind) -> RadiotapStream<'a> {
+        self.present = Some(kind);
+        self
+    }
+}
+
+impl<'a> Iterator for RadiotapStream<'a> {
+    type Item = Result<(Radiotap, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.offset >= self.data.len() {
+                return None;
+            }
+
+            let mut cursor = ByteReader::new(&self.data[self.offset..]);
+            let frame = match cursor.read_u32() {
+                Ok(length) => cursor.get_ref().get(4..4 + length as usize),
+                Err(_) => None,
+            };
+
+            let frame = match frame {
+                Some(frame) => frame,
+                None => {
+                    let offset = self.offset;
+                    self.offset = self.data.len();
+                    return Some(Err(Error::IncompleteError { offset, kind: None }));
+                }
+            };
+            self.offset += 4 + frame.len();
+
+            if let Some(kind) = self.present {
+                match Header::from_bytes(frame) {
+                    Ok(header) if !header.has_field(kind) => continue,
+                    Ok(_) => {}
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            return Some(Radiotap::parse(frame));
+        }
+    }
+}
+
+/// Builds a Radiotap capture field by field.
+///
+/// Fields are written in canonical present bit order with the alignment
+/// required by each field, regardless of the order the setters are called in.
+///
+/// ```
+/// use radiotap::{field::Rate, Radiotap, RadiotapBuilder};
+///
+/// let capture = RadiotapBuilder::new().rate(Rate { value: 6.0 }).build();
+/// let radiotap = Radiotap::from_bytes(&capture).unwrap();
+/// assert_eq!(radiotap.rate, Some(Rate { value: 6.0 }));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RadiotapBuilder {
+    radiotap: Radiotap,
+}
+
+impl RadiotapBuilder {
+    /// Returns a builder with no fields set.
+    pub fn new() -> RadiotapBuilder {
+        RadiotapBuilder::default()
+    }
+
+    /// Sets the [TSFT](field/struct.TSFT.html) field.
+    pub fn tsft(mut self, tsft: TSFT) -> RadiotapBuilder {
+        self.radiotap.tsft = Some(tsft);
+        self
+    }
+
+    /// Sets the [Flags](field/struct.Flags.html) field.
+    pub fn flags(mut self, flags: Flags) -> RadiotapBuilder {
+        self.radiotap.flags = Some(flags);
+        self
+    }
+
+    /// Sets the [Rate](field/struct.Rate.html) field.
+    pub fn rate(mut self, rate: Rate) -> RadiotapBuilder {
+        self.radiotap.rate = Some(rate);
+        self
+    }
+
+    /// Sets the [Channel](field/struct.Channel.html) field.
+    pub fn channel(mut self, channel: Channel) -> RadiotapBuilder {
+        self.radiotap.channel = Some(channel);
+        self
+    }
+
+    /// Sets the [FHSS](field/struct.FHSS.html) field.
+    pub fn fhss(mut self, fhss: FHSS) -> RadiotapBuilder {
+        self.radiotap.fhss = Some(fhss);
+        self
+    }
+
+    /// Sets the [AntennaSignal](field/struct.AntennaSignal.html) field.
+    pub fn antenna_signal(mut self, antenna_signal: AntennaSignal) -> RadiotapBuilder {
+        self.radiotap.antenna_signal = Some(antenna_signal);
+        self
+    }
+
+    /// Sets the [AntennaNoise](field/struct.AntennaNoise.html) field.
+    pub fn antenna_noise(mut self, antenna_noise: AntennaNoise) -> RadiotapBuilder {
+        self.radiotap.antenna_noise = Some(antenna_noise);
+        self
+    }
+
+    /// Sets the [LockQuality](field/struct.LockQuality.html) field.
+    pub fn lock_quality(mut self, lock_quality: LockQuality) -> RadiotapBuilder {
+        self.radiotap.lock_quality = Some(lock_quality);
+        self
+    }
+
+    /// Sets the [TxAttenuation](field/struct.TxAttenuation.html) field.
+    pub fn tx_attenuation(mut self, tx_attenuation: TxAttenuation) -> RadiotapBuilder {
+        self.radiotap.tx_attenuation = Some(tx_attenuation);
+        self
+    }
+
+    /// Sets the [TxAttenuationDb](field/struct.TxAttenuationDb.html) field.
+    pub fn tx_attenuation_db(mut self, tx_attenuation_db: TxAttenuationDb) -> RadiotapBuilder {
+        self.radiotap.tx_attenuation_db = Some(tx_attenuation_db);
+        self
+    }
+
+    /// Sets the [TxPower](field/struct.TxPower.html) field.
+    pub fn tx_power(mut self, tx_power: TxPower) -> RadiotapBuilder {
+        self.radiotap.tx_power = Some(tx_power);
+        self
+    }
+
+    /// Sets the [Antenna](field/struct.Antenna.html) field.
+    pub fn antenna(mut self, antenna: Antenna) -> RadiotapBuilder {
+        self.radiotap.antenna = Some(antenna);
+        self
+    }
+
+    /// Sets the [AntennaSignalDb](field/struct.AntennaSignalDb.html) field.
+    pub fn antenna_signal_db(mut self, antenna_signal_db: AntennaSignalDb) -> RadiotapBuilder {
+        self.radiotap.antenna_signal_db = Some(antenna_signal_db);
+        self
+    }
+
+    /// Sets the [AntennaNoiseDb](field/struct.AntennaNoiseDb.html) field.
+    pub fn antenna_noise_db(mut self, antenna_noise_db: AntennaNoiseDb) -> RadiotapBuilder {
+        self.radiotap.antenna_noise_db = Some(antenna_noise_db);
+        self
+    }
+
+    /// Sets the [RxFlags](field/struct.RxFlags.html) field.
+    pub fn rx_flags(mut self, rx_flags: RxFlags) -> RadiotapBuilder {
+        self.radiotap.rx_flags = Some(rx_flags);
+        self
+    }
+
+    /// Sets the [TxFlags](field/struct.TxFlags.html) field.
+    pub fn tx_flags(mut self, tx_flags: TxFlags) -> RadiotapBuilder {
+        self.radiotap.tx_flags = Some(tx_flags);
+        self
+    }
+
+    /// Sets the [RTSRetries](field/struct.RTSRetries.html) field.
+    pub fn rts_retries(mut self, rts_retries: RTSRetries) -> RadiotapBuilder {
+        self.radiotap.rts_retries = Some(rts_retries);
+        self
+    }
+
+    /// Sets the [DataRetries](field/struct.DataRetries.html) field.
+    pub fn data_retries(mut self, data_retries: DataRetries) -> RadiotapBuilder {
+        self.radiotap.data_retries = Some(data_retries);
+        self
+    }
+
+    /// Sets the [XChannel](field/struct.XChannel.html) field.
+    pub fn xchannel(mut self, xchannel: XChannel) -> RadiotapBuilder {
+        self.radiotap.xchannel = Some(xchannel);
+        self
+    }
+
+    /// Sets the [MCS](field/struct.MCS.html) field.
+    pub fn mcs(mut self, mcs: MCS) -> RadiotapBuilder {
+        self.radiotap.mcs = Some(mcs);
+        self
+    }
+
+    /// Sets the [AMPDUStatus](field/struct.AMPDUStatus.html) field.
+    pub fn ampdu_status(mut self, ampdu_status: AMPDUStatus) -> RadiotapBuilder {
+        self.radiotap.ampdu_status = Some(ampdu_status);
+        self
+    }
+
+    /// Sets the [VHT](field/struct.VHT.html) field.
+    pub fn vht(mut self, vht: VHT) -> RadiotapBuilder {
+        self.radiotap.vht = Some(vht);
+        self
+    }
+
+    /// Sets the [Timestamp](field/struct.Timestamp.html) field.
+    pub fn timestamp(mut self, timestamp: Timestamp) -> RadiotapBuilder {
+        self.radiotap.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the [HE](field/struct.HE.html) field.
+    pub fn he(mut self, he: HE) -> RadiotapBuilder {
+        self.radiotap.he = Some(he);
+        self
+    }
+
+    /// Sets the [HEMU](field/struct.HEMU.html) field.
+    pub fn he_mu(mut self, he_mu: HEMU) -> RadiotapBuilder {
+        self.radiotap.he_mu = Some(he_mu);
+        self
+    }
+
+    /// Sets the [ZeroLengthPSDU](field/struct.ZeroLengthPSDU.html) field.
+    pub fn zero_length_psdu(mut self, zero_length_psdu: ZeroLengthPSDU) -> RadiotapBuilder {
+        self.radiotap.zero_length_psdu = Some(zero_length_psdu);
+        self
+    }
+
+    /// Sets the [LSIG](field/struct.LSIG.html) field.
+    pub fn lsig(mut self, lsig: LSIG) -> RadiotapBuilder {
+        self.radiotap.lsig = Some(lsig);
+        self
+    }
+
+    /// Sets the [USIG](field/struct.USIG.html) field.
+    pub fn usig(mut self, usig: USIG) -> RadiotapBuilder {
+        self.radiotap.usig = Some(usig);
+        self
+    }
+
+    /// Sets the [EHT](field/struct.EHT.html) field.
+    pub fn eht(mut self, eht: EHT) -> RadiotapBuilder {
+        self.radiotap.eht = Some(eht);
+        self
+    }
+
+    /// Returns the encoded Radiotap capture.
+    pub fn build(&self) -> Vec<u8> {
+        self.radiotap.to_bytes()
+    }
+}
+
+/// Starts a builder from the fields of a parsed capture, so that they can be
+/// modified and the capture encoded again.
+impl From<Radiotap> for RadiotapBuilder {
+    fn from(radiotap: Radiotap) -> RadiotapBuilder {
+        RadiotapBuilder { radiotap }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::ext::*;
+
+    #[test]
+    fn good_vendor() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        assert_eq!(
+            Radiotap::from_bytes(&frame).unwrap().rate.unwrap(),
+            Rate { value: 2.0 }
+        );
+    }
+
+    #[test]
+    fn namespace_reset() {
+        let frame = [
+            0, 0, 27, 0, 6, 0, 0, 192, 1, 0, 0, 160, 32, 0, 0, 0, 16, 12, 0, 17, 34, 0, 2, 0, 222,
+            173, 186,
+        ];
+
+        let fields: Vec<(Kind, &[u8])> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|result| result.unwrap())
+            .collect();
+        let vns = VendorNamespace {
+            oui: [0, 17, 34],
+            sub_namespace: 0,
+            skip_length: 2,
+        };
+        assert_eq!(
+            fields,
+            vec![
+                (Kind::Flags, &[16][..]),
+                (Kind::Rate, &[12][..]),
+                (Kind::VendorNamespace(Some(vns)), &[222, 173][..]),
+                (Kind::AntennaSignal, &[186][..]),
+            ]
+        );
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.rate.unwrap(), Rate { value: 6.0 });
+        assert_eq!(
+            radiotap.antenna_signal.unwrap(),
+            AntennaSignal { value: -70 }
+        );
+    }
+
+    struct FakeNamespace {
+        oui: Oui,
+        value: Option<u16>,
+    }
+
+    impl FakeNamespace {
+        fn new(oui: Oui) -> FakeNamespace {
+            FakeNamespace { oui, value: None }
+        }
+    }
+
+    impl Namespace for FakeNamespace {
+        fn oui(&self) -> Oui {
+            self.oui
+        }
+
+        fn layout(&self, bit: u8) -> Option<(u64, usize)> {
+            match bit {
+                0 => Some((2, 2)),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, bit: u8, data: &[u8]) -> Result<()> {
+            assert_eq!(bit, 0);
+            self.value = Some(u16::from(data[0]) | u16::from(data[1]) << 8);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn vendor_namespace() {
+        let frame = [
+            0, 0, 27, 0, 6, 0, 0, 192, 1, 0, 0, 160, 32, 0, 0, 0, 16, 12, 0, 17, 34, 0, 2, 0, 222,
+            173, 186,
+        ];
+
+        let mut namespace = FakeNamespace::new([0, 17, 34]);
+        let kinds: Vec<Kind> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .vendor(&mut namespace)
+            .map(|result| result.unwrap().0)
+            .collect();
+        let vns = VendorNamespace {
+            oui: [0, 17, 34],
+            sub_namespace: 0,
+            skip_length: 2,
+        };
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::Flags,
+                Kind::Rate,
+                Kind::VendorNamespace(Some(vns)),
+                Kind::VendorField(0),
+                Kind::AntennaSignal,
+            ]
+        );
+        assert_eq!(namespace.value, Some(0xadde));
+    }
+
+    #[test]
+    fn multiple_vendor_namespaces() {
+        // Flags, two vendor namespaces with a field each, and antenna signal
+        let frame = [
+            0, 0, 39, 0, 2, 0, 0, 192, 1, 0, 0, 192, 1, 0, 0, 160, 32, 0, 0, 0, 16, 0, 0, 17, 34,
+            0, 2, 0, 222, 173, 0, 17, 35, 0, 2, 0, 52, 18, 186,
+        ];
+
+        let mut first = FakeNamespace::new([0, 17, 34]);
+        let mut second = FakeNamespace::new([0, 17, 35]);
+        let kinds: Vec<Kind> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .vendor(&mut first)
+            .vendor(&mut second)
+            .map(|result| result.unwrap().0)
+            .filter(|kind| !matches!(kind, Kind::VendorNamespace(_)))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::Flags,
+                Kind::VendorField(0),
+                Kind::VendorField(0),
+                Kind::AntennaSignal,
+            ]
+        );
+        assert_eq!(first.value, Some(0xadde));
+        assert_eq!(second.value, Some(0x1234));
+
+        let mut first = FakeNamespace::new([0, 17, 34]);
+        let mut second = FakeNamespace::new([0, 17, 35]);
+        let mut namespaces = CaptureNamespace::new();
+        namespaces.register(&mut first);
+        namespaces.register(&mut second);
+        assert!(namespaces.contains([0, 17, 35]));
+        assert!(!namespaces.contains([0, 17, 36]));
+        namespaces
+            .get_mut([0, 17, 35])
+            .unwrap()
+            .update(0, &[1, 0])
+            .unwrap();
+        drop(namespaces);
+        assert_eq!(first.value, None);
+        assert_eq!(second.value, Some(1));
+    }
+
+    #[test]
+    fn unknown_vendor_namespace() {
+        let frame = [
+            0, 0, 27, 0, 6, 0, 0, 192, 1, 0, 0, 160, 32, 0, 0, 0, 16, 12, 0, 17, 35, 0, 2, 0, 222,
+            173, 186,
+        ];
+
+        let mut namespace = FakeNamespace::new([0, 17, 34]);
+        let kinds: Vec<Kind> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .vendor(&mut namespace)
+            .map(|result| result.unwrap().0)
+            .collect();
+        assert_eq!(kinds.len(), 4);
+        assert_eq!(kinds[3], Kind::AntennaSignal);
+        assert_eq!(namespace.value, None);
+    }
+
+    #[test]
+    fn unsupported_field() {
+        // Flags and an unknown field in bit 32
+        let frame = [0, 0, 16, 0, 2, 0, 0, 128, 1, 0, 0, 0, 16, 0, 255, 255];
+
+        let kinds: Vec<Kind> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|result| result.unwrap().0)
+            .collect();
+        assert_eq!(kinds, vec![Kind::Flags]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_reader() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let mut data = frame.to_vec();
+        data.extend_from_slice(&[0xd4, 0]);
+        let mut reader = std::io::Cursor::new(data);
+
+        let (radiotap, capture) = Radiotap::from_reader(&mut reader).unwrap();
+        assert_eq!(radiotap, Radiotap::from_bytes(&frame).unwrap());
+        assert_eq!(capture, frame);
+        assert_eq!(reader.position(), 56);
+
+        match Radiotap::from_reader(&mut std::io::Cursor::new(&frame[..20])).unwrap_err() {
+            Error::IncompleteError {
+                offset: 20,
+                kind: None,
+            } => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        };
+
+        match Radiotap::from_reader(&mut std::io::Cursor::new(&frame[..2])).unwrap_err() {
+            Error::IncompleteError {
+                offset: 2,
+                kind: None,
+            } => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        };
+
+        match Radiotap::from_reader(&mut std::io::Cursor::new([0, 0, 0, 0])).unwrap_err() {
+            Error::InvalidLength => {}
+            e => panic!("Error not InvalidLength: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn owned_field() {
+        let frame = vec![
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let fields: Vec<OwnedField> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|field| field.unwrap().into())
+            .collect();
+        drop(frame);
+
+        let kinds: Vec<Kind> = fields.iter().map(|field| field.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::TSFT,
+                Kind::Flags,
+                Kind::Channel,
+                Kind::AntennaSignal,
+                Kind::AntennaNoise,
+                Kind::Antenna,
+                Kind::XChannel,
+                Kind::AMPDUStatus,
+                Kind::VHT,
+            ]
+        );
+        assert_eq!(
+            fields[0].decode().unwrap(),
+            Some(FieldValue::TSFT(TSFT {
+                value: 2_593_857_465
+            }))
+        );
+        assert_eq!(
+            fields[3].decode().unwrap(),
+            Some(FieldValue::AntennaSignal(AntennaSignal { value: -43 }))
+        );
+        match fields[8].decode().unwrap() {
+            Some(FieldValue::VHT(vht)) => assert_eq!(vht.bw.unwrap().bandwidth, 80),
+            value => panic!("unexpected value: {:?}", value),
+        }
+
+        let vendor = OwnedField {
+            kind: Kind::VendorField(0),
+            data: vec![1],
+        };
+        assert_eq!(vendor.decode().unwrap(), None);
+    }
+
+    #[test]
+    fn coverage() {
+        // Flags, HE-MU-other-user, and zero length PSDU
+        let frame = [0, 0, 17, 0, 2, 0, 0, 6, 16, 0, 1, 2, 3, 4, 5, 6, 2];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.coverage(),
+            Coverage {
+                understood: 2,
+                skipped: vec![25],
+            }
+        );
+
+        // Flags, an unknown field in bit 32, and zero length PSDU
+        let frame = [0, 0, 16, 0, 2, 0, 0, 128, 1, 0, 0, 4, 16, 0, 0, 0];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.flags.is_some());
+        assert!(radiotap.zero_length_psdu.is_none());
+        assert_eq!(
+            radiotap.coverage(),
+            Coverage {
+                understood: 1,
+                skipped: vec![32, 58],
+            }
+        );
+
+        let radiotap = Radiotap::from_bytes(&[0, 0, 9, 0, 2, 0, 0, 0, 16]).unwrap();
+        assert_eq!(radiotap.coverage().understood, 1);
+        assert!(radiotap.coverage().skipped.is_empty());
+    }
+
+    #[test]
+    fn unparsed_field() {
+        // Flags, HE-MU-other-user, and zero length PSDU
+        let frame = [0, 0, 17, 0, 2, 0, 0, 6, 16, 0, 1, 2, 3, 4, 5, 6, 1];
+
+        let fields: Vec<(Kind, &[u8])> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|result| result.unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Kind::Flags, &[16][..]),
+                (Kind::Unparsed(25), &[1, 2, 3, 4, 5, 6][..]),
+                (Kind::ZeroLengthPSDU, &[1][..]),
+            ]
+        );
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(
+            radiotap.zero_length_psdu.unwrap().psdu_type,
+            PSDUType::NotCaptured
+        );
+        assert_eq!(radiotap.field(Kind::Unparsed(25)), None);
+    }
+
+    #[test]
+    fn split() {
+        let mut packet = vec![
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        packet.extend_from_slice(&[0xd4, 0, 0, 0, 1, 2, 3, 4, 5, 6]);
+
+        let (radiotap, frame) = Radiotap::split(&packet).unwrap();
+        assert_eq!(radiotap.header.length, 56);
+        assert_eq!(frame, &packet[radiotap.header.length..]);
+        assert_eq!(radiotap, Radiotap::from_bytes(&packet).unwrap());
+
+        let (_, frame) = Radiotap::split(&packet[..56]).unwrap();
+        assert!(frame.is_empty());
+    }
+
+    #[test]
+    fn parse_rest() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4, 128, 0, 0, 0,
+        ];
+
+        let (radiotap, rest) = Radiotap::parse(&frame).unwrap();
+        assert_eq!(radiotap.header.length, 39);
+        assert_eq!(radiotap.rate.unwrap(), Rate { value: 2.0 });
+        assert_eq!(rest, [128, 0, 0, 0]);
+    }
+
+    #[test]
+    fn to_bytes() {
+        let radiotap = Radiotap {
+            header: Header {
+                version: 0,
+                length: 18,
+                size: 8,
+                present: Present::from(
+                    &[Kind::Flags, Kind::Channel, Kind::AntennaSignal, Kind::MCS][..],
+                ),
+                tlv: false,
+                skipped: Vec::new(),
+                resets: Vec::new(),
+            },
+            flags: Some(Flags {
+                cfp: false,
+                preamble: true,
+                wep: false,
+                fragmentation: false,
+                fcs: true,
+                data_pad: false,
+                bad_fcs: false,
+                sgi: false,
+            }),
+            channel: Some(Channel {
+                freq: 5180,
+                flags: ChannelFlags {
+                    turbo: false,
+                    cck: false,
+                    ofdm: true,
+                    ghz2: false,
+                    ghz5: true,
+                    passive: false,
+                    dynamic: false,
+                    gfsk: false,
+                },
+            }),
+            antenna_signal: Some(AntennaSignal { value: -72 }),
+            mcs: Some(MCS {
+                bw: Some(Bandwidth::new(0).unwrap()),
+                index: Some(7),
+                gi: Some(GuardInterval::Short),
+                format: Some(HTFormat::Mixed),
+                fec: Some(FEC::LDPC),
+                stbc: Some(1),
+                ness: None,
+                datarate: Some(72.2),
+            }),
+            ..Default::default()
+        };
+
+        let bytes = radiotap.to_bytes();
+        assert_eq!(
+            bytes,
+            [0, 0, 18, 0, 42, 0, 8, 0, 18, 0, 60, 20, 64, 1, 184, 63, 52, 7]
+        );
+        assert_eq!(Radiotap::from_bytes(&bytes).unwrap(), radiotap);
+    }
+
+    #[test]
+    fn to_bytes_roundtrip() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            Radiotap::from_bytes(&radiotap.to_bytes()).unwrap(),
+            radiotap
+        );
+    }
+
+    #[test]
+    fn display() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            format!("{}", radiotap),
+            "TSFT: 2593857465 us\n\
+             Channel: 5500 MHz (ch 100, 5 GHz OFDM)\n\
+             Signal: -43 dBm\n\
+             Noise: -90 dBm\n\
+             Antenna: 1\n\
+             VHT: 80 MHz, long GI, MCS 7 NSS 3 (877.5 Mbps)"
+        );
+
+        let mcs = MCS {
+            bw: Some(Bandwidth::new(0).unwrap()),
+            index: Some(7),
+            gi: Some(GuardInterval::Short),
+            datarate: Some(72.2),
+            ..Default::default()
+        };
+        let radiotap = Radiotap {
+            rate: Some(Rate { value: 5.5 }),
+            mcs: Some(mcs),
+            ..Default::default()
+        };
+        assert_eq!(
+            format!("{}", radiotap),
+            "Rate: 5.5 Mbps\nMCS: MCS 7, 20 MHz, short GI, 72.2 Mbps"
+        );
+        assert_eq!(format!("{}", Radiotap::default()), "");
+    }
+
+    #[test]
+    fn frame_ok() {
+        let flags = |value| Flags::from_bytes(&[value]).unwrap();
+
+        // FCS present and not bad
+        let radiotap = RadiotapBuilder::new().flags(flags(0x10)).build();
+        assert_eq!(
+            Radiotap::from_bytes(&radiotap).unwrap().frame_ok(),
+            Some(true)
+        );
+
+        // Bad FCS
+        let radiotap = RadiotapBuilder::new().flags(flags(0x50)).build();
+        assert_eq!(
+            Radiotap::from_bytes(&radiotap).unwrap().frame_ok(),
+            Some(false)
+        );
+
+        // Bad PLCP, even with a good FCS
+        let radiotap = RadiotapBuilder::new()
+            .flags(flags(0x10))
+            .rx_flags(RxFlags { bad_plcp: true })
+            .build();
+        assert_eq!(
+            Radiotap::from_bytes(&radiotap).unwrap().frame_ok(),
+            Some(false)
+        );
+
+        // No FCS
+        let radiotap = RadiotapBuilder::new()
+            .flags(flags(0x00))
+            .rx_flags(RxFlags { bad_plcp: false })
+            .build();
+        assert_eq!(Radiotap::from_bytes(&radiotap).unwrap().frame_ok(), None);
+        assert_eq!(Radiotap::default().frame_ok(), None);
+    }
+
+    #[test]
+    fn channel_info() {
+        let channel = Channel::from_bytes(&[0x7c, 0x15, 0x40, 0x01]).unwrap();
+        let xchannel = XChannel::from_bytes(&[0x40, 0x01, 0x02, 0, 0x3c, 0x14, 36, 20]).unwrap();
+
+        let radiotap = Radiotap::from_bytes(&RadiotapBuilder::new().channel(channel).build());
+        assert_eq!(
+            radiotap.unwrap().channel_info(),
+            Some(ChannelInfo {
+                freq: 5500,
+                channel: Some(100),
+                band: Some(Band::GHz5),
+                width: None,
+            })
+        );
+
+        let radiotap = Radiotap::from_bytes(&RadiotapBuilder::new().xchannel(xchannel).build());
+        let xchannel_info = ChannelInfo {
+            freq: 5180,
+            channel: Some(36),
+            band: Some(Band::GHz5),
+            width: Some(40),
+        };
+        assert_eq!(radiotap.unwrap().channel_info(), Some(xchannel_info));
+
+        let radiotap = RadiotapBuilder::new()
+            .channel(channel)
+            .xchannel(xchannel)
+            .build();
+        let radiotap = Radiotap::from_bytes(&radiotap).unwrap();
+        assert_eq!(radiotap.channel_info(), Some(xchannel_info));
+
+        // A 6 GHz channel flagged as 5 GHz
+        let channel = Channel::from_bytes(&[0xe3, 0x17, 0x40, 0x01]).unwrap();
+        assert_eq!(
+            ChannelInfo::from(channel),
+            ChannelInfo {
+                freq: 6115,
+                channel: Some(33),
+                band: Some(Band::GHz6),
+                width: None,
+            }
+        );
+
+        assert_eq!(Radiotap::default().channel_info(), None);
+    }
+
+    #[test]
+    fn bandwidth_mhz() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let mut radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.bandwidth_mhz(), Some(80));
+
+        radiotap.mcs = Some(MCS {
+            bw: Some(Bandwidth::new(1).unwrap()),
+            ..Default::default()
+        });
+        assert_eq!(radiotap.bandwidth_mhz(), Some(80));
+        radiotap.vht = None;
+        assert_eq!(radiotap.bandwidth_mhz(), Some(40));
+
+        radiotap.he = Some(HE::from_bytes(&[0, 0x40, 0, 0, 0, 0, 0, 0, 0x03, 0, 0, 0]).unwrap());
+        assert_eq!(radiotap.bandwidth_mhz(), Some(160));
+
+        radiotap.usig = Some(USIG {
+            bw: Some(EHTBandwidth::BW320Ch1),
+            ..USIG::from_bytes(&[0; 12]).unwrap()
+        });
+        assert_eq!(radiotap.bandwidth_mhz(), Some(320));
+
+        assert_eq!(Radiotap::default().bandwidth_mhz(), None);
+    }
+
+    #[test]
+    fn data_rate_mbps() {
+        let rate = Radiotap::from_bytes(&RadiotapBuilder::new().rate(Rate { value: 5.5 }).build());
+        assert_eq!(rate.unwrap().data_rate_mbps(), Some(5.5));
+
+        // MCS 7, 20 MHz, long GI
+        let mcs = Radiotap::from_bytes(&[0, 0, 11, 0, 0, 0, 8, 0, 7, 0, 7]).unwrap();
+        assert_eq!(mcs.rate, None);
+        assert_eq!(mcs.data_rate_mbps(), Some(65.0));
+
+        // The fastest of two VHT users, 80 MHz, long GI
+        let mut vht = [0; 12];
+        vht[0] = 0x44;
+        vht[3] = 4;
+        vht[4] = 0x12;
+        vht[5] = 0x73;
+        let mut capture = vec![0, 0, 20, 0, 0, 0, 32, 0];
+        capture.extend_from_slice(&vht);
+        let vht = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(vht.mcs, None);
+        assert_eq!(vht.data_rate_mbps(), Some(877.5));
+
+        // The legacy rate is not used when a newer PHY field is present
+        let he = Radiotap {
+            rate: Some(Rate { value: 6.0 }),
+            he: Some(HE::from_bytes(&[0; 12]).unwrap()),
+            ..Radiotap::default()
+        };
+        assert_eq!(he.primary_phy(), Some(PhyField::HE));
+        assert_eq!(he.data_rate_mbps(), None);
+
+        // Nor when the VHT datarate is unknown
+        let vht = Radiotap {
+            rate: Some(Rate { value: 6.0 }),
+            mcs: Some(MCS::from_bytes(&[0, 0, 7]).unwrap()),
+            vht: Some(VHT::from_bytes(&[0; 12]).unwrap()),
+            ..Radiotap::default()
+        };
+        assert_eq!(vht.data_rate_mbps(), None);
+
+        assert_eq!(Radiotap::default().data_rate_mbps(), None);
+    }
+
+    #[test]
+    fn debug_dump() {
+        let capture = RadiotapBuilder::new()
+            .flags(Flags::from_bytes(&[0x10]).unwrap())
+            .rate(Rate { value: 6.0 })
+            .channel(Channel::from_bytes(&[0x3c, 0x14, 0x40, 0x01]).unwrap())
+            .build();
+
+        let dump = Radiotap::debug_dump(&capture).unwrap();
+        let lines: Vec<_> = dump.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2], "  2 Rate                  1      9    1 0c");
+        assert_eq!(
+            lines[3],
+            "  3 Channel               2     10    4 3c 14 40 01"
+        );
+
+        assert!(Radiotap::debug_dump(&capture[..11]).is_err());
+    }
+
+    #[test]
+    fn airtime_us() {
+        // 1500 bytes at 54 Mbps OFDM, 56 symbols of 216 bits
+        let radiotap = Radiotap {
+            rate: Some(Rate { value: 54.0 }),
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.airtime_us(1500), Some(244.0));
+
+        // 1500 bytes at 11 Mbps CCK with a long and a short preamble
+        let mut radiotap = Radiotap {
+            rate: Some(Rate { value: 11.0 }),
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.airtime_us(1500), Some(1283.0));
+        radiotap.flags = Some(Flags::from_bytes(&[0x02]).unwrap());
+        assert_eq!(radiotap.airtime_us(1500), Some(1187.0));
+
+        // 1500 bytes at HT MCS 7, 20 MHz, long GI, 47 symbols of 260 bits
+        let radiotap = Radiotap {
+            mcs: Some(MCS::from_bytes(&[0x07, 0x00, 0x07]).unwrap()),
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.airtime_us(1500), Some(224.0));
+
+        // 1500 bytes at VHT MCS 7, 3 streams, 80 MHz, long GI, 4 symbols of
+        // 3510 bits
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.airtime_us(1500), Some(68.0));
+
+        let radiotap = Radiotap {
+            he: Some(HE::from_bytes(&[0; 12]).unwrap()),
+            ..radiotap
+        };
+        assert_eq!(radiotap.airtime_us(1500), None);
+        assert_eq!(Radiotap::default().airtime_us(1500), None);
+    }
+
+    #[test]
+    fn decode_into() {
+        let vht = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0, 0xd4, 0x00,
+        ];
+        let rate = RadiotapBuilder::new().rate(Rate { value: 6.0 }).build();
+
+        let mut radiotap = Radiotap::default();
+        let rest = radiotap.decode_into(&vht).unwrap();
+        assert_eq!(rest, [0xd4, 0x00]);
+        assert_eq!(radiotap, Radiotap::from_bytes(&vht).unwrap());
+
+        let rest = radiotap.decode_into(&rate).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(radiotap, Radiotap::from_bytes(&rate).unwrap());
+        assert_eq!(radiotap.vht, None);
+        assert!(radiotap.per_antenna_signals().is_empty());
+    }
+
+    #[test]
+    fn primary_phy() {
+        assert_eq!(Radiotap::default().primary_phy(), None);
+
+        let legacy = Radiotap {
+            rate: Some(Rate { value: 6.0 }),
+            ..Radiotap::default()
+        };
+        assert_eq!(legacy.primary_phy(), Some(PhyField::Legacy));
+        assert_eq!(legacy.phy_mode(), Some(PhyMode::OFDM));
+
+        // A driver reporting the legacy rate alongside MCS 7
+        let both = Radiotap {
+            mcs: Some(MCS::from_bytes(&[0x07, 0x00, 0x07]).unwrap()),
+            ..legacy.clone()
+        };
+        assert_eq!(both.primary_phy(), Some(PhyField::MCS));
+        assert_eq!(both.phy_mode(), Some(PhyMode::HT));
+        assert_eq!(both.data_rate_mbps(), Some(65.0));
+
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let vht = Radiotap {
+            rate: legacy.rate,
+            mcs: both.mcs,
+            ..Radiotap::from_bytes(&frame).unwrap()
+        };
+        assert_eq!(vht.primary_phy(), Some(PhyField::VHT));
+        assert_eq!(vht.phy_mode(), Some(PhyMode::VHT));
+        assert_eq!(vht.data_rate_mbps(), Some(877.5));
+
+        let he = Radiotap {
+            he: Some(HE::from_bytes(&[0; 12]).unwrap()),
+            ..vht
+        };
+        assert_eq!(he.primary_phy(), Some(PhyField::HE));
+        assert_eq!(he.phy_mode(), Some(PhyMode::HE));
+    }
+
+    #[test]
+    fn map_keys() {
+        use std::collections::HashSet;
+
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        // Another capture on the same channel at a later time
+        let mut later = frame;
+        later[8] = 186;
+        // A capture on channel 36
+        let mut other = frame;
+        other[18] = 0x3c;
+        other[19] = 0x14;
+
+        let mut keys = HashSet::new();
+        for frame in [frame, later, other, frame].iter() {
+            let radiotap = Radiotap::from_bytes(frame).unwrap();
+            keys.insert((
+                radiotap.channel,
+                radiotap.data_rate_mbps().map(RateKey::new),
+                radiotap.vht.map(|vht| vht.bw),
+            ));
+        }
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().any(
+            |(channel, rate, _)| channel.unwrap().freq == 5180 && *rate == Some(RateKey(8775))
+        ));
+
+        assert_eq!(RateKey::new(877.49), RateKey::new(877.5));
+        assert_eq!(RateKey::new(7.2).mbps(), 7.2);
+        assert_eq!(RateKey::new(-1.0), RateKey(0));
+    }
+
+    #[test]
+    fn approx_eq() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+
+        // A rate table rounded differently
+        let mut rounded = radiotap.clone();
+        let mut vht = rounded.vht.unwrap();
+        vht.users[0].as_mut().unwrap().datarate = Some(877.49);
+        rounded.vht = Some(vht);
+        assert_ne!(radiotap, rounded);
+        assert!(radiotap.approx_eq(&rounded, 0.05));
+        assert!(!radiotap.approx_eq(&rounded, 0.001));
+
+        let mut other = rounded.clone();
+        other.antenna_signal = Some(AntennaSignal { value: -44 });
+        assert!(!radiotap.approx_eq(&other, 0.05));
+
+        let rate = Radiotap {
+            rate: Some(Rate { value: 5.5 }),
+            ..Default::default()
+        };
+        let rounded = Radiotap {
+            rate: Some(Rate { value: 5.51 }),
+            ..Default::default()
+        };
+        assert!(rate.approx_eq(&rounded, 0.05));
+        assert!(!rate.approx_eq(&Radiotap::default(), 0.05));
+    }
+
+    #[test]
+    fn to_kv() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let kv = Radiotap::from_bytes(&frame).unwrap().to_kv();
+
+        for pair in [
+            ("tsft_us", "2593857465"),
+            ("flags", "wep,fcs"),
+            ("channel_freq", "5500"),
+            ("channel_number", "100"),
+            ("antenna_signal_dbm", "-43"),
+            ("vht_bandwidth_mhz", "80"),
+            ("vht_mcs_index", "7"),
+            ("vht_nss", "3"),
+            ("vht_datarate_mbps", "877.5"),
+        ] {
+            assert!(
+                kv.contains(&(pair.0, pair.1.to_string())),
+                "{:?} not in {:?}",
+                pair,
+                kv
+            );
+        }
+        // Absent fields are omitted
+        assert!(kv.iter().all(|(key, _)| !key.starts_with("mcs_")));
+        assert!(kv.iter().all(|(key, _)| *key != "rate_mbps"));
+
+        assert!(Radiotap::default().to_kv().is_empty());
+    }
+
+    #[test]
+    fn mac_time() {
+        let tsft = TSFT { value: 2593857465 };
+        let timestamp = Timestamp {
+            timestamp: 1500,
+            unit: TimeUnit::Milliseconds,
+            position: SamplingPosition::StartMPDU,
+            accuracy: None,
+            counter_32bit: false,
+        };
+
+        let capture = RadiotapBuilder::new().tsft(tsft).build();
+        let radiotap = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(radiotap.mac_time(), Some(MacTime::TSFT(2593857465)));
+
+        let capture = RadiotapBuilder::new().timestamp(timestamp).build();
+        let radiotap = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(radiotap.mac_time(), Some(MacTime::Timestamp(1_500_000)));
+        assert_eq!(radiotap.mac_time().unwrap().micros(), 1_500_000);
+
+        // The TSFT is preferred
+        let capture = RadiotapBuilder::new()
+            .tsft(tsft)
+            .timestamp(timestamp)
+            .build();
+        let radiotap = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(radiotap.mac_time(), Some(MacTime::TSFT(2593857465)));
+
+        assert_eq!(Radiotap::default().mac_time(), None);
+    }
+
+    #[test]
+    fn signal_quality() {
+        let radiotap = Radiotap {
+            antenna_signal: Some(AntennaSignal { value: -70 }),
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.signal_quality(), Some(60));
+        assert_eq!(radiotap.antenna_signal, Some(AntennaSignal { value: -70 }));
+        assert_eq!(Radiotap::default().signal_quality(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn combined_rssi_dbm() {
+        // A combined signal, then a signal and antenna for each of two chains
+        // at -73 dBm, which sum to double the power
+        let frame = [
+            0, 0, 21, 0, 0x20, 0, 0, 0xa0, 0x20, 0x08, 0, 0xa0, 0x20, 0x08, 0, 0, 206, 183, 0, 183,
+            1,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.combined_rssi_dbm(), Some(-70));
+
+        let radiotap = Radiotap {
+            antenna_signals: vec![(0, -40), (1, -50), (2, -60)],
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.combined_rssi_dbm(), Some(-40));
+
+        let radiotap = Radiotap {
+            antenna_signals: vec![(1, -73)],
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.combined_rssi_dbm(), Some(-73));
+
+        let capture = RadiotapBuilder::new()
+            .antenna_signal(AntennaSignal { value: -43 })
+            .build();
+        let radiotap = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(radiotap.combined_rssi_dbm(), Some(-43));
+        assert_eq!(Radiotap::default().combined_rssi_dbm(), None);
+    }
+
+    #[test]
+    fn per_antenna_signals() {
+        // A combined signal, then a signal and antenna in a radiotap namespace
+        // for each of two chains
+        let frame = [
+            0, 0, 21, 0, 0x20, 0, 0, 0xa0, 0x20, 0x08, 0, 0xa0, 0x20, 0x08, 0, 0, 206, 186, 0, 181,
+            1,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.per_antenna_signals(), [(0, -70), (1, -75)]);
+        assert_eq!(radiotap.antenna_signal, Some(AntennaSignal { value: -75 }));
+        assert_eq!(radiotap.antenna, Some(Antenna { value: 1 }));
+
+        // A signal and antenna for each of three chains, with the noise of the
+        // second chain in between, separated by two namespace resets
+        let frame = [
+            0, 0, 23, 0, 0x20, 0x08, 0, 0xa0, 0x60, 0x08, 0, 0xa0, 0x20, 0x08, 0, 0, 196, 0, 186,
+            160, 1, 181, 2,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.per_antenna_signals(),
+            [(0, -60), (1, -70), (2, -75)]
+        );
+        assert_eq!(radiotap.antenna_noise, Some(AntennaNoise { value: -96 }));
+
+        // A signal and antenna, then an antenna without a signal in the next
+        // namespace, which has no signal to pair with
+        let frame = [0, 0, 15, 0, 0x20, 0x08, 0, 0xa0, 0, 0x08, 0, 0, 186, 0, 1];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.per_antenna_signals(), [(0, -70)]);
+        assert_eq!(radiotap.antenna, Some(Antenna { value: 1 }));
+
+        // A signal alone, then an antenna alone in the next namespace
+        let frame = [0, 0, 14, 0, 0x20, 0, 0, 0xa0, 0, 0x08, 0, 0, 186, 1];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.per_antenna_signals().is_empty());
+        assert_eq!(radiotap.antenna_signal, Some(AntennaSignal { value: -70 }));
+        assert_eq!(radiotap.antenna, Some(Antenna { value: 1 }));
+
+        let mut fields = RadiotapIterator::from_bytes(&frame).unwrap().into_iter();
+        let mut namespaces = Vec::new();
+        while let Some(result) = fields.next() {
+            namespaces.push((result.unwrap().0, fields.namespace()));
+        }
+        assert_eq!(namespaces, [(Kind::AntennaSignal, 0), (Kind::Antenna, 1)]);
+    }
+
+    #[test]
+    fn stream_filter_present() {
+        let vht = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let rate = RadiotapBuilder::new().rate(Rate { value: 6.0 }).build();
+        // A frame without VHT whose Channel field is cut short, which is
+        // skipped without being decoded
+        let truncated = [0, 0, 10, 0, 8, 0, 0, 0, 108, 9];
+        let frames: [&[u8]; 4] = [&rate, &vht, &truncated, &vht];
+
+        let mut buffer = Vec::new();
+        for frame in frames.iter() {
+            buffer.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(frame);
+        }
+
+        let parsed: Vec<_> = RadiotapStream::new(&buffer)
+            .filter_present(Kind::VHT)
+            .map(|result| result.unwrap().0)
+            .collect();
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.iter().all(|radiotap| radiotap.vht.is_some()));
+
+        assert_eq!(RadiotapStream::new(&buffer).count(), 4);
+    }
+
+    #[test]
+    fn stream() {
+        let frames = [
+            (
+                RadiotapBuilder::new().rate(Rate { value: 1.0 }).build(),
+                vec![1],
+            ),
+            (
+                RadiotapBuilder::new().rate(Rate { value: 2.0 }).build(),
+                vec![2, 2],
+            ),
+            (
+                RadiotapBuilder::new().rate(Rate { value: 5.5 }).build(),
+                vec![],
+            ),
+        ];
+
+        let mut buffer = Vec::new();
+        for (capture, payload) in &frames {
+            let length = (capture.len() + payload.len()) as u32;
+            buffer.extend_from_slice(&length.to_le_bytes());
+            buffer.extend_from_slice(capture);
+            buffer.extend_from_slice(payload);
+        }
+
+        let parsed: Vec<_> = RadiotapStream::new(&buffer)
+            .map(|result| result.unwrap())
+            .map(|(radiotap, payload)| (radiotap.rate.unwrap().value, payload))
+            .collect();
+        assert_eq!(parsed, [(1.0, &[1][..]), (2.0, &[2, 2]), (5.5, &[])]);
+
+        // A frame that overruns the buffer ends the stream
+        let end = buffer.len();
+        buffer.extend_from_slice(&[100, 0, 0, 0, 0, 0]);
+        let mut stream = RadiotapStream::new(&buffer).skip(3);
+        match stream.next() {
+            Some(Err(Error::IncompleteError { offset, .. })) => assert_eq!(offset, end),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn field() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.field(Kind::Channel),
+            radiotap.channel.map(FieldValue::Channel)
+        );
+        assert!(radiotap.field(Kind::Channel).is_some());
+        assert_eq!(radiotap.field(Kind::Rate), None);
+        assert_eq!(radiotap.field(Kind::TLV(0)), None);
+
+        // Every present field has a value of its kind
+        for &kind in radiotap.header.present.iter() {
+            assert_eq!(radiotap.field(kind).unwrap().kind(), kind);
+        }
+
+        let radiotap = Radiotap::from_bytes(&[0, 0, 9, 0, 2, 0, 0, 0, 16]).unwrap();
+        assert_eq!(radiotap.field(Kind::VHT), None);
+    }
+
+    #[test]
+    fn builder_from_radiotap() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+
+        let bytes = RadiotapBuilder::from(radiotap.clone())
+            .antenna_signal(AntennaSignal { value: -70 })
+            .build();
+        let rebuilt = Radiotap::from_bytes(&bytes).unwrap();
+
+        assert_eq!(rebuilt.antenna_signal, Some(AntennaSignal { value: -70 }));
+        assert_eq!(rebuilt.per_antenna_signals(), [(1, -70)]);
+        assert_eq!(
+            Radiotap {
+                antenna_signal: radiotap.antenna_signal,
+                antenna_signals: radiotap.antenna_signals.clone(),
+                ..rebuilt
+            },
+            radiotap
+        );
+    }
+
+    #[test]
+    fn phy_mode() {
+        // 802.11b at 11 Mbps on channel 6 with a short preamble
+        let capture = RadiotapBuilder::new()
+            .flags(Flags::from_bytes(&[0x02]).unwrap())
+            .rate(Rate { value: 11.0 })
+            .channel(Channel::from_bytes(&[0x85, 0x09, 0xa0, 0x00]).unwrap())
+            .build();
+        let radiotap = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(radiotap.phy_mode(), Some(PhyMode::CCK));
+
+        // 802.11a at 54 Mbps on channel 36
+        let capture = RadiotapBuilder::new()
+            .rate(Rate { value: 54.0 })
+            .channel(Channel::from_bytes(&[0x3c, 0x14, 0x40, 0x01]).unwrap())
+            .build();
+        let radiotap = Radiotap::from_bytes(&capture).unwrap();
+        assert_eq!(radiotap.phy_mode(), Some(PhyMode::OFDM));
+
+        let radiotap = Radiotap {
+            rate: Some(Rate { value: 2.0 }),
+            ..Radiotap::default()
+        };
+        assert_eq!(radiotap.phy_mode(), Some(PhyMode::DSSS));
+
+        let radiotap = Radiotap {
+            rate: None,
+            ..radiotap
+        };
+        assert_eq!(radiotap.phy_mode(), None);
+
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.phy_mode(), Some(PhyMode::VHT));
+    }
+
+    #[test]
+    fn builder() {
+        let tsft = TSFT { value: 0x1234_5678 };
+        let antenna_signal = AntennaSignal { value: -60 };
+        let timestamp = Timestamp {
+            timestamp: 42,
+            unit: TimeUnit::Microseconds,
+            position: SamplingPosition::StartPLCP,
+            accuracy: None,
+            counter_32bit: false,
+        };
+
+        // Setters may be called in any order
+        let bytes = RadiotapBuilder::new()
+            .timestamp(timestamp)
+            .antenna_signal(antenna_signal)
+            .tsft(tsft)
+            .build();
+
+        let radiotap = Radiotap::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            radiotap.header.present[..],
+            [Kind::TSFT, Kind::AntennaSignal, Kind::Timestamp]
+        );
+        assert_eq!(radiotap.header.length, bytes.len());
+        assert_eq!(radiotap.tsft, Some(tsft));
+        assert_eq!(radiotap.antenna_signal, Some(antenna_signal));
+        assert_eq!(radiotap.timestamp, Some(timestamp));
+        assert_eq!(radiotap.flags, None);
+    }
+
+    #[test]
+    fn he() {
+        let frame = [
+            0, 0, 28, 0, 42, 0, 128, 0, 16, 0, 60, 20, 64, 1, 196, 0, 252, 195, 118, 0, 42, 41, 0,
+            0, 130, 1, 2, 127,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.header.present[..],
+            [Kind::Flags, Kind::Channel, Kind::AntennaSignal, Kind::HE]
+        );
+        assert_eq!(radiotap.antenna_signal, Some(AntennaSignal { value: -60 }));
+        assert_eq!(
+            radiotap.he,
+            Some(HE {
+                format: HEFormat::SU,
+                bss_color: Some(42),
+                beam_change: Some(false),
+                ul_dl: Some(false),
+                index: Some(9),
+                dcm: Some(false),
+                fec: Some(FEC::LDPC),
+                ldpc_extra: Some(false),
+                stbc: Some(false),
+                bw: Some(HEBandwidth::BW80),
+                gi: Some(HEGuardInterval::Ns800),
+                ltf_size: Some(LTFSize::X2),
+                ltf_symbols: Some(2),
+                txbf: Some(false),
+                pe_disambiguity: Some(false),
+                txop: Some(127),
+                doppler: Some(false),
+                nsts: Some(2),
+            })
+        );
+        assert_eq!(radiotap.to_bytes(), frame.to_vec());
+    }
+
+    #[test]
+    fn he_mu() {
+        let frame = [
+            0, 0, 34, 0, 2, 0, 128, 1, 16, 0, 38, 64, 2, 0, 5, 4, 0, 0, 22, 0, 0, 0, 82, 209, 37,
+            0, 96, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.header.present[..],
+            [Kind::Flags, Kind::HE, Kind::HEMU]
+        );
+
+        let he = radiotap.he.unwrap();
+        assert_eq!(he.format, HEFormat::MU);
+        assert_eq!(he.bss_color, Some(5));
+        assert_eq!(he.index, Some(4));
+        assert_eq!(he.bw, Some(HEBandwidth::RU106));
+        assert_eq!(he.gi, Some(HEGuardInterval::Ns1600));
+
+        assert_eq!(
+            radiotap.he_mu,
+            Some(HEMU {
+                sig_b_index: Some(2),
+                sig_b_dcm: Some(false),
+                sig_b_compression: Some(false),
+                sig_b_symbols: Some(3),
+                bw: Some(HEBandwidth::BW40),
+                preamble_puncturing: None,
+                center_ru_channel1: Some(false),
+                center_ru_channel2: None,
+                ru_channel1: Some([96, 0, 0, 0]),
+                ru_channel2: None,
+            })
+        );
+        assert_eq!(radiotap.to_bytes(), frame.to_vec());
+    }
+
+    #[test]
+    fn zero_length_psdu() {
+        let frame = [0, 0, 10, 0, 32, 0, 0, 4, 196, 255];
+
+        let (radiotap, rest) = Radiotap::parse(&frame).unwrap();
+        assert_eq!(
+            radiotap.header.present[..],
+            [Kind::AntennaSignal, Kind::ZeroLengthPSDU]
+        );
+        assert_eq!(radiotap.antenna_signal, Some(AntennaSignal { value: -60 }));
+        assert_eq!(
+            radiotap.zero_length_psdu,
+            Some(ZeroLengthPSDU {
+                psdu_type: PSDUType::Vendor
+            })
+        );
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn lsig() {
+        let frame = [0, 0, 14, 0, 2, 0, 0, 8, 0, 0, 3, 0, 219, 16];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.header.present[..], [Kind::Flags, Kind::LSIG]);
+        assert_eq!(
+            radiotap.lsig,
+            Some(LSIG {
+                rate: Some(11),
+                length: Some(269),
+            })
+        );
+        assert_eq!(radiotap.to_bytes(), frame.to_vec());
+    }
+
+    #[test]
+    fn usig() {
+        let frame = [
+            0, 0, 24, 0, 0, 0, 0, 16, 33, 0, 12, 0, 223, 0, 82, 33, 0, 0, 0, 0, 192, 0, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.header.present.is_empty());
+        let usig = radiotap.usig.unwrap();
+        assert_eq!(usig.bw, Some(EHTBandwidth::BW320Ch1));
+        assert_eq!(usig.bss_color, Some(42));
+        assert_eq!(radiotap.to_bytes(), frame.to_vec());
+
+        let fields: Vec<_> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|field| field.unwrap().0)
+            .collect();
+        assert_eq!(fields, [Kind::USIG]);
+    }
+
+    #[test]
+    fn eht() {
+        let usig = USIG::from_bytes(&[0x1f, 0, 0x52, 0x21, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        let eht = EHT {
+            gi: Some(HEGuardInterval::Ns1600),
+            nss: Some(2),
+            users: vec![EHTUser {
+                index: Some(13),
+                fec: Some(FEC::LDPC),
+                data_for_user: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let bytes = RadiotapBuilder::new()
+            .flags(Flags::from_bytes(&[0]).unwrap())
+            .usig(usig)
+            .eht(eht.clone())
+            .build();
+        let radiotap = Radiotap::from_bytes(&bytes).unwrap();
+        assert_eq!(radiotap.header.present[..], [Kind::Flags]);
+        assert!(radiotap.header.tlv);
+        assert_eq!(radiotap.usig, Some(usig));
+        assert_eq!(radiotap.eht, Some(eht));
+
+        let kinds: Vec<_> = RadiotapIterator::from_bytes(&bytes)
+            .unwrap()
+            .into_iter()
+            .map(|field| field.unwrap().0)
+            .collect();
+        assert_eq!(kinds, [Kind::Flags, Kind::USIG, Kind::EHT]);
+    }
+
+    #[test]
+    fn tlv() {
+        let frame = [
+            0, 0, 28, 0, 34, 0, 0, 16, 16, 196, 0, 0, 27, 0, 4, 0, 3, 0, 219, 16, 52, 18, 3, 0, 1,
+            2, 3, 0,
+        ];
+
+        let iterator = RadiotapIterator::from_bytes(&frame).unwrap();
+        assert!(iterator.header.tlv);
+        let fields: Vec<_> = iterator.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Kind::Flags, &[16][..]),
+                (Kind::AntennaSignal, &[196][..]),
+                (Kind::TLV(27), &[3, 0, 219, 16][..]),
+                (Kind::TLV(0x1234), &[1, 2, 3][..]),
+            ]
+        );
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.antenna_signal, Some(AntennaSignal { value: -60 }));
+        assert_eq!(
+            radiotap.lsig,
+            Some(LSIG {
+                rate: Some(11),
+                length: Some(269),
+            })
+        );
+    }
+
+    #[test]
+    fn bad_tlv() {
+        let frame = [0, 0, 16, 0, 2, 0, 0, 16, 16, 0, 0, 0, 1, 0, 8, 0];
+
+        let fields: Vec<_> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(fields.len(), 2);
+        match fields[1] {
+            Err(Error::IncompleteError { .. }) => {}
+            ref e => panic!("Error not IncompleteError: {:?}", e),
+        };
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let json = serde_json::to_string(&radiotap).unwrap();
+        assert!(json.contains(r#""present":["TSFT","Flags","Channel","#));
+        assert_eq!(serde_json::from_str::<Radiotap>(&json).unwrap(), radiotap);
+    }
+
+    #[test]
+    fn bad_version() {
+        let frame = [
+            1, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::UnsupportedVersion => {}
+            e => panic!("Error not UnsupportedVersion: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn bad_header_length() {
+        let frame = [
+            0, 0, 40, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::InvalidLength => {}
+            e => panic!("Error not InvalidLength: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn truncated_header() {
+        match Radiotap::from_bytes(&[0, 0, 8, 0]).unwrap_err() {
+            Error::InvalidLength => {}
+            e => panic!("Error not InvalidLength: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn detect_header() {
+        let capture = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        assert_eq!(super::detect_header(&capture), HeaderKind::Radiotap);
+
+        // A Prism header in either byte order, with a 144 byte message
+        let mut prism = [0; 144];
+        prism[..8].copy_from_slice(&[0x44, 0, 0, 0, 144, 0, 0, 0]);
+        assert_eq!(super::detect_header(&prism), HeaderKind::Prism);
+        prism[..8].copy_from_slice(&[0, 0, 0, 0x44, 0, 0, 0, 144]);
+        assert_eq!(super::detect_header(&prism), HeaderKind::Prism);
+
+        let avs = [0x80, 0x21, 0x10, 0x01, 0, 0, 0, 64];
+        assert_eq!(super::detect_header(&avs), HeaderKind::Avs);
+
+        // A truncated Radiotap header, or one with a bad length
+        assert_eq!(super::detect_header(&capture[..40]), HeaderKind::Unknown);
+        assert_eq!(super::detect_header(&[0, 0, 4, 0]), HeaderKind::Unknown);
+        assert_eq!(super::detect_header(&[]), HeaderKind::Unknown);
+    }
+
+    #[test]
+    fn parse_into() {
+        #[derive(Default)]
+        struct Signal {
+            signal: Option<i8>,
+            fields: usize,
+        }
+
+        impl Namespace for Signal {
+            fn oui(&self) -> field::Oui {
+                [0; 3]
+            }
+
+            fn layout(&self, _bit: u8) -> Option<(u64, usize)> {
+                None
+            }
+
+            fn update(&mut self, bit: u8, data: &[u8]) -> Result<()> {
+                self.fields += 1;
+                if bit == Kind::AntennaSignal.bit() {
+                    self.signal = Some(AntennaSignal::from_bytes(data)?.value);
+                }
+                Ok(())
+            }
+        }
+
+        let mut frame = vec![
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        frame.extend_from_slice(&[0xd4, 0]);
+
+        let mut signal = Signal::default();
+        let payload = super::parse_into(&frame, &mut signal).unwrap();
+        assert_eq!(signal.signal, Some(-43));
+        assert_eq!(signal.fields, 9);
+        assert_eq!(payload, [0xd4, 0]);
+
+        let mut signal = Signal::default();
+        assert!(super::parse_into(&frame[..40], &mut signal).is_err());
+    }
+
+    #[test]
+    fn zero_header_length() {
+        match Radiotap::from_bytes(&[0, 0, 0, 0, 0, 0, 0, 0]).unwrap_err() {
+            Error::InvalidLength => {}
+            e => panic!("Error not InvalidLength: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn short_header_length() {
+        // The length doesn't cover the second present word
+        let frame = [0, 0, 8, 0, 0, 0, 0, 128, 2, 0, 0, 0, 0, 0, 0, 0];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::InvalidFormat { offset: 8, .. } => {}
+            e => panic!("Error not InvalidFormat: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn present_words_overrun() {
+        // The present words continue past the end of the capture
+        let frame = [0, 0, 8, 0, 0, 0, 0, 128];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::InvalidFormat {
+                offset: 8,
+                kind: None,
+                value: None,
+            } => {}
+            e => panic!("Error not InvalidFormat: {:?}", e),
+        };
+
+        // The present words continue past the length into the 802.11 frame
+        let frame = [0, 0, 12, 0, 0, 0, 0, 128, 0, 0, 0, 128, 0, 0, 0, 0, 8, 2];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::InvalidFormat {
+                offset: 12,
+                kind: None,
+                value: None,
+            } => {}
+            e => panic!("Error not InvalidFormat: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn bad_actual_length() {
+        let frame = [
+            0, 0, 39, 0, 47, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::IncompleteError { .. } => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn trailing_header_padding() {
+        // Flags and Antenna signal, then 4 bytes of padding in the header
+        let frame = [0, 0, 14, 0, 34, 0, 0, 0, 0x10, 0xd5, 0, 0, 0, 0, 0xaa, 0xbb];
+
+        let fields = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            fields,
+            [
+                (Kind::Flags, &frame[8..9]),
+                (Kind::AntennaSignal, &frame[9..10])
+            ]
+        );
+
+        let (radiotap, payload) = Radiotap::parse(&frame).unwrap();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(radiotap.antenna_signal.unwrap().value, -43);
+        assert_eq!(payload, [0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn truncated_vht() {
+        let mut frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        // Cut the capture short in the middle of the VHT field
+        frame[2] = 50;
+
+        let err = Radiotap::from_bytes(&frame[..50]).unwrap_err();
+        match err {
+            Error::IncompleteError {
+                offset: 44,
+                kind: Some(Kind::VHT),
+            } => {}
+            ref e => panic!("Error not IncompleteError at VHT: {:?}", e),
+        };
+        assert_eq!(
+            err.to_string(),
+            "The given data is not a complete Radiotap capture (VHT field at offset 44)"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_trait_object() {
+        fn assert_error<E: std::error::Error + Send + Sync + 'static>() {}
+        assert_error::<Error>();
+
+        // The bounds of error reporting crates such as anyhow
+        fn parse(
+            input: &[u8],
+        ) -> result::Result<Radiotap, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(Radiotap::from_bytes(input)?)
+        }
+
+        let err = parse(&[0, 0, 56, 0, 0, 0, 0, 0]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Radiotap::from_bytes(&[0, 0, 56, 0, 0, 0, 0, 0])
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(err.downcast_ref::<Error>().is_some());
+    }
+
+    #[test]
+    fn invalid_field() {
+        // The VHT field has an invalid bandwidth
+        let mut frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        frame[47] = 31;
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::InvalidFormat {
+                offset: 44,
+                kind: Some(Kind::VHT),
+                value: Some(31),
+            } => {}
+            e => panic!("Error not InvalidFormat at VHT: {:?}", e),
+        };
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_with_raw() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let (radiotap, raw) = Radiotap::parse_with_raw(&frame).unwrap();
+        assert_eq!(radiotap, Radiotap::from_bytes(&frame).unwrap());
+        assert_eq!(raw.len(), 9);
+        assert_eq!(raw[&Kind::Channel], [124, 21, 64, 1]);
+        assert_eq!(raw[&Kind::AntennaSignal], [213]);
+        assert!(!raw.contains_key(&Kind::MCS));
+
+        assert!(Radiotap::parse_with_raw(&frame[..40]).is_err());
+    }
+
+    #[test]
+    fn lenient() {
+        // The VHT field has an invalid bandwidth
+        let mut frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        frame[47] = 31;
+
+        let (radiotap, errors) = Radiotap::from_bytes_lenient(&frame).unwrap();
+        assert_eq!(radiotap.tsft.unwrap().value, 2_593_857_465);
+        assert_eq!(radiotap.antenna_signal.unwrap().value, -43);
+        assert!(radiotap.ampdu_status.is_some());
+        assert!(radiotap.vht.is_none());
+
+        assert_eq!(errors.len(), 1);
+        match errors[0] {
+            (
+                Kind::VHT,
+                Error::InvalidFormat {
+                    offset: 44,
+                    kind: Some(Kind::VHT),
+                    value: Some(31),
+                },
+            ) => {}
+            ref e => panic!("Error not InvalidFormat at VHT: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn lenient_truncated() {
+        let mut frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        frame[2] = 50;
+
+        let (radiotap, errors) = Radiotap::from_bytes_lenient(&frame[..50]).unwrap();
+        assert!(radiotap.ampdu_status.is_some());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, Kind::VHT);
+    }
+
+    #[test]
+    fn parse_with_options() {
+        // Flags, rate, an unregistered vendor namespace with 3 bytes of data,
+        // and antenna signal
+        let frame = [
+            0, 0, 28, 0, 6, 0, 0, 192, 3, 0, 0, 160, 32, 0, 0, 0, 16, 12, 0, 17, 34, 0, 3, 0, 42,
+            0, 200, 186, 0xd4,
+        ];
+        let expected = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(expected.antenna_signal.unwrap().value, -70);
+
+        let options = ParseOptions::default();
+        let (radiotap, vendor, rest) = Radiotap::parse_with_options(&frame, &options).unwrap();
+        assert_eq!(radiotap, expected);
+        assert!(vendor.is_empty());
+        assert_eq!(rest, [0xd4]);
+
+        let options = ParseOptions {
+            on_unknown_vendor: UnknownPolicy::Collect,
+            ..ParseOptions::default()
+        };
+        let (radiotap, vendor, rest) = Radiotap::parse_with_options(&frame, &options).unwrap();
+        assert_eq!(radiotap, expected);
+        assert_eq!(vendor, [([0, 17, 34], &[42, 0, 200][..])]);
+        assert_eq!(rest, [0xd4]);
+
+        let options = ParseOptions {
+            on_unknown_vendor: UnknownPolicy::Error,
+            ..ParseOptions::default()
+        };
+        match Radiotap::parse_with_options(&frame, &options) {
+            Err(Error::UnsupportedField) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn tolerate_nested() {
+        let inner = RadiotapBuilder::new()
+            .rate(Rate { value: 6.0 })
+            .antenna_signal(AntennaSignal { value: -43 })
+            .build();
+        let mut frame = RadiotapBuilder::new().rate(Rate { value: 1.0 }).build();
+        frame.extend_from_slice(&inner);
+        frame.extend_from_slice(&[0xd4, 0, 0, 0]);
+
+        let options = ParseOptions::default();
+        let (radiotap, _, rest) = Radiotap::parse_with_options(&frame, &options).unwrap();
+        assert_eq!(radiotap.rate, Some(Rate { value: 1.0 }));
+        assert_eq!(rest.len(), inner.len() + 4);
+
+        let options = ParseOptions {
+            tolerate_nested: true,
+            ..ParseOptions::default()
+        };
+        let (radiotap, _, rest) = Radiotap::parse_with_options(&frame, &options).unwrap();
+        assert_eq!(radiotap, Radiotap::from_bytes(&inner).unwrap());
+        assert_eq!(rest, [0xd4, 0, 0, 0]);
+
+        // An 802.11 frame is not mistaken for a nested capture
+        let mut frame = RadiotapBuilder::new().rate(Rate { value: 1.0 }).build();
+        frame.extend_from_slice(&[0xd4, 0, 0, 0]);
+        let (radiotap, _, rest) = Radiotap::parse_with_options(&frame, &options).unwrap();
+        assert_eq!(radiotap.rate, Some(Rate { value: 1.0 }));
+        assert_eq!(rest, [0xd4, 0, 0, 0]);
+    }
+
+    #[test]
+    fn bad_vendor() {
+        let frame = [
+            0, 0, 34, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::IncompleteError { .. } => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn truncated_and_corrupted() {
+        // The seeds of the fuzz target, parsing must never panic when they are
+        // truncated or any byte is corrupted
+        let frames: [&[u8]; 3] = [
+            &[
+                0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1,
+                213, 166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255,
+                1, 80, 4, 115, 0, 0, 0, 1, 63, 0, 0,
+            ],
+            &[
+                0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+                160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+            ],
+            &[
+                0, 0, 27, 0, 6, 0, 0, 192, 1, 0, 0, 160, 32, 0, 0, 0, 16, 12, 0, 17, 34, 0, 2, 0,
+                222, 173, 186,
+            ],
+        ];
+
+        // A field in the ninth present word of a namespace, past the bits a
+        // `u8` can index
+        let mut frame = vec![0, 0, 44, 0];
+        for _ in 0..8 {
+            frame.extend_from_slice(&[0, 0, 0, 0x80]);
+        }
+        frame.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        match Radiotap::from_bytes(&frame) {
+            Err(Error::InvalidFormat {
+                offset: 36,
+                kind: None,
+                value: None,
+            }) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+        // And in a vendor namespace
+        frame[7] = 0xc0;
+        assert!(Radiotap::from_bytes(&frame).is_err());
+
+        let parse = |data: &[u8]| {
+            let _ = Radiotap::from_bytes(data);
+            let _ = Radiotap::from_bytes_lenient(data);
+            if let Ok(iterator) = RadiotapIterator::from_bytes(data) {
+                for field in iterator.into_iter() {
+                    let _ = field;
+                }
+            }
+        };
+
+        for frame in frames.iter() {
+            for end in 0..frame.len() {
+                parse(&frame[..end]);
+            }
+            for i in 0..frame.len() {
+                for &value in [0x00, 0x7f, 0x80, 0xff].iter() {
+                    let mut corrupted = frame.to_vec();
+                    corrupted[i] = value;
+                    parse(&corrupted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn offset_overflow() {
+        // A vendor namespace claiming u16::MAX bytes in a tiny capture
+        let frame = [
+            0, 0, 18, 0, 0, 0, 0, 192, 0, 0, 0, 0, 0, 17, 34, 0, 255, 255,
+        ];
+        let mut iter = RadiotapIterator::from_bytes(&frame).unwrap().into_iter();
+        match iter.next() {
+            Some(Err(Error::IncompleteError { offset: 12, .. })) => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        }
+
+        // A vendor field so large that its end does not fit in a usize
+        struct HugeNamespace;
+
+        impl Namespace for HugeNamespace {
+            fn oui(&self) -> Oui {
+                [0, 17, 34]
+            }
+
+            fn layout(&self, _: u8) -> Option<(u64, usize)> {
+                Some((1, usize::MAX))
+            }
+
+            fn update(&mut self, _: u8, _: &[u8]) -> Result<()> {
+                panic!("field should not be decoded");
+            }
+        }
+
+        let frame = [
+            0, 0, 22, 0, 0, 0, 0, 192, 1, 0, 0, 0, 0, 17, 34, 0, 4, 0, 1, 2, 3, 4,
+        ];
+        let mut namespace = HugeNamespace;
+        let mut iter = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .vendor(&mut namespace);
+        assert!(matches!(
+            iter.next(),
+            Some(Ok((Kind::VendorNamespace(_), _)))
+        ));
+        match iter.next() {
+            Some(Err(Error::IncompleteError { offset: 18, .. })) => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        }
     }
 }
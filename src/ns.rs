@@ -0,0 +1,62 @@
+//! Vendor namespace definitions.
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use crate::{field::Oui, Result};
+
+/// A Radiotap vendor namespace.
+///
+/// Implement this trait to parse the fields in a vendor namespace. The
+/// namespace needs to know the layout of each of its fields so that they can
+/// be located, the fields are then passed to the namespace as they are
+/// iterated over.
+pub trait Namespace {
+    /// Returns the OUI of the vendor namespace.
+    fn oui(&self) -> Oui;
+
+    /// Returns the align and size of the field with the given present bit, or
+    /// `None` if the field is unknown.
+    fn layout(&self, bit: u8) -> Option<(u64, usize)>;
+
+    /// Updates the namespace with the data of the field with the given present
+    /// bit.
+    fn update(&mut self, bit: u8, data: &[u8]) -> Result<()>;
+}
+
+/// The vendor namespaces registered for a capture, keyed by OUI.
+#[derive(Default)]
+pub struct CaptureNamespace<'a> {
+    namespaces: BTreeMap<Oui, &'a mut dyn Namespace>,
+}
+
+impl<'a> CaptureNamespace<'a> {
+    /// Returns a new empty `CaptureNamespace`.
+    pub fn new() -> CaptureNamespace<'a> {
+        CaptureNamespace::default()
+    }
+
+    /// Registers a vendor namespace, replacing any namespace already registered
+    /// with the same OUI.
+    pub fn register(&mut self, namespace: &'a mut dyn Namespace) {
+        self.namespaces.insert(namespace.oui(), namespace);
+    }
+
+    /// Returns whether a vendor namespace is registered with the given OUI.
+    pub fn contains(&self, oui: Oui) -> bool {
+        self.namespaces.contains_key(&oui)
+    }
+
+    /// Returns the vendor namespace registered with the given OUI.
+    pub fn get_mut(&mut self, oui: Oui) -> Option<&mut (dyn Namespace + 'a)> {
+        self.namespaces
+            .get_mut(&oui)
+            .map(|namespace| &mut **namespace)
+    }
+}
+
+impl fmt::Debug for CaptureNamespace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.namespaces.keys()).finish()
+    }
+}
@@ -0,0 +1,208 @@
+//! Locating the body of the captured 802.11 frame.
+//!
+//! The [frame_control](fn.frame_control.html) function classifies the frame
+//! from its frame control field.
+//!
+//! When the [Flags](../field/struct.Flags.html) field has `data_pad` set, the
+//! 802.11 header is padded to a 32-bit boundary before the frame body.
+//!
+//! ```
+//! use radiotap::{payload, Radiotap};
+//!
+//! let capture = [
+//!     0, 0, 9, 0, 2, 0, 0, 0, 0x20, // Radiotap with data padding
+//!     0x88, 0x01, 0, 0, // QoS data to the AP
+//!     1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, // Addresses
+//!     0, 0, 0, 0, // Sequence and QoS control
+//!     0, 0, // Padding
+//!     0xaa, 0xaa, 0x03,
+//! ];
+//!
+//! let (radiotap, frame) = Radiotap::parse(&capture).unwrap();
+//! let offset = payload::aligned_offset(&radiotap.flags.unwrap(), frame);
+//! assert_eq!(&frame[offset..], [0xaa, 0xaa, 0x03]);
+//! ```
+
+use crate::field::Flags;
+
+/// The type of an 802.11 frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameType {
+    /// Management frames, type 0, such as beacons and probes.
+    Management,
+    /// Control frames, type 1, such as ACK and RTS.
+    Control,
+    /// Data frames, type 2, including QoS data.
+    Data,
+    /// Extension frames, type 3, such as DMG beacons.
+    Extension,
+}
+
+/// The frame control field at the start of an 802.11 frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameControl {
+    /// The protocol version, always 0.
+    pub version: u8,
+    /// The type of the frame.
+    pub frame_type: FrameType,
+    /// The subtype of the frame, e.g. 8 for a beacon or QoS data frame.
+    pub subtype: u8,
+    /// The frame is going to the distribution system.
+    pub to_ds: bool,
+    /// The frame is coming from the distribution system.
+    pub from_ds: bool,
+    /// More fragments of the frame follow.
+    pub more_fragments: bool,
+    /// The frame is a retransmission.
+    pub retry: bool,
+    /// The sender is going into power save mode.
+    pub power_management: bool,
+    /// More frames are buffered for the receiver.
+    pub more_data: bool,
+    /// The frame body is encrypted.
+    pub protected: bool,
+    /// In QoS data and management frames, an HT control field is present.
+    pub order: bool,
+}
+
+/// Returns the frame control field decoded from the first 2 bytes of the
+/// 802.11 frame, or `None` if the frame is too short.
+///
+/// ```
+/// use radiotap::{frame_control, FrameType};
+///
+/// let control = frame_control(&[0x80, 0x00]).unwrap();
+/// assert_eq!(control.frame_type, FrameType::Management);
+/// assert_eq!(control.subtype, 8);
+/// ```
+pub fn frame_control(frame: &[u8]) -> Option<FrameControl> {
+    let (first, flags) = match frame {
+        [first, flags, ..] => (*first, *flags),
+        _ => return None,
+    };
+
+    Some(FrameControl {
+        version: first & 0x03,
+        frame_type: match (first >> 2) & 0x03 {
+            0 => FrameType::Management,
+            1 => FrameType::Control,
+            2 => FrameType::Data,
+            _ => FrameType::Extension,
+        },
+        subtype: first >> 4,
+        to_ds: flags & 0x01 != 0,
+        from_ds: flags & 0x02 != 0,
+        more_fragments: flags & 0x04 != 0,
+        retry: flags & 0x08 != 0,
+        power_management: flags & 0x10 != 0,
+        more_data: flags & 0x20 != 0,
+        protected: flags & 0x40 != 0,
+        order: flags & 0x80 != 0,
+    })
+}
+
+/// Returns the offset of the frame body in the 802.11 frame, the length of the
+/// 802.11 header plus any padding indicated by the flags.
+///
+/// Only the start of the frame is needed, the header length is computed from
+/// the frame control field. Returns 0 if the frame is too short to contain the
+/// frame control field. The offset may be past the end of a truncated frame.
+pub fn aligned_offset(flags: &Flags, frame: &[u8]) -> usize {
+    let length = header_length(frame);
+    if flags.data_pad {
+        (length + 3) & !3
+    } else {
+        length
+    }
+}
+
+/// Returns the length of the 802.11 header from the frame control field.
+fn header_length(frame: &[u8]) -> usize {
+    let control = match frame_control(frame) {
+        Some(control) => control,
+        None => return 0,
+    };
+    // The order bit indicates an HT control field in QoS data and management
+    // frames
+    let ht_control = if control.order { 4 } else { 0 };
+
+    match control.frame_type {
+        FrameType::Management => 24 + ht_control,
+        // CTS and ACK only have a receiver address
+        FrameType::Control => match control.subtype {
+            12 | 13 => 10,
+            _ => 16,
+        },
+        FrameType::Data => {
+            let mut length = 24;
+            // Both to and from DS have a fourth address
+            if control.to_ds && control.from_ds {
+                length += 6;
+            }
+            // QoS control
+            if control.subtype & 0x08 != 0 {
+                length += 2 + ht_control;
+            }
+            length
+        }
+        // Extension frames share at least the frame control, duration and
+        // address fields
+        FrameType::Extension => 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Field;
+
+    #[test]
+    fn aligned_offset() {
+        let padded = Flags::from_bytes(&[0x20]).unwrap();
+        let unpadded = Flags::from_bytes(&[0x00]).unwrap();
+
+        // QoS data, 26 byte header with 2 bytes of padding
+        let qos_data = [0x88, 0x01];
+        assert_eq!(super::aligned_offset(&padded, &qos_data), 28);
+        assert_eq!(super::aligned_offset(&unpadded, &qos_data), 26);
+
+        // QoS data with four addresses and HT control
+        let qos_data = [0x88, 0x83];
+        assert_eq!(super::aligned_offset(&padded, &qos_data), 36);
+
+        // Data and beacons are already aligned
+        assert_eq!(super::aligned_offset(&padded, &[0x08, 0x02]), 24);
+        assert_eq!(super::aligned_offset(&padded, &[0x80, 0x00]), 24);
+
+        // ACK
+        assert_eq!(super::aligned_offset(&unpadded, &[0xd4, 0x00]), 10);
+        assert_eq!(super::aligned_offset(&padded, &[0xd4, 0x00]), 12);
+
+        assert_eq!(super::aligned_offset(&padded, &[0x88]), 0);
+    }
+
+    #[test]
+    fn frame_control() {
+        let beacon = super::frame_control(&[0x80, 0x00]).unwrap();
+        assert_eq!(beacon.frame_type, FrameType::Management);
+        assert_eq!(beacon.subtype, 8);
+        assert!(!beacon.to_ds && !beacon.from_ds);
+
+        // QoS data to the AP, retried and protected
+        let qos_data = super::frame_control(&[0x88, 0x49]).unwrap();
+        assert_eq!(qos_data.frame_type, FrameType::Data);
+        assert_eq!(qos_data.subtype, 8);
+        assert!(qos_data.to_ds && !qos_data.from_ds);
+        assert!(qos_data.retry && qos_data.protected);
+        assert!(!qos_data.more_data && !qos_data.order);
+
+        let ack = super::frame_control(&[0xd4, 0x00]).unwrap();
+        assert_eq!(ack.frame_type, FrameType::Control);
+        assert_eq!(ack.subtype, 13);
+        assert_eq!(ack.version, 0);
+
+        assert_eq!(super::frame_control(&[0x80]), None);
+    }
+}
@@ -0,0 +1,366 @@
+//! Reading Radiotap captures from pcap and pcapng files.
+//!
+//! Requires the `pcap` feature.
+//!
+//! ```no_run
+//! use std::fs::File;
+//!
+//! let file = File::open("capture.pcap").unwrap();
+//! for record in radiotap::pcap::from_reader(file).unwrap() {
+//!     let (radiotap, payload) = record.unwrap();
+//!     println!("{:?} {} bytes", radiotap.antenna_signal, payload.len());
+//! }
+//! ```
+
+use std::io::{self, Read};
+
+use crate::{Error, Radiotap, Result};
+
+/// The link type of Radiotap captures, `LINKTYPE_IEEE802_11_RADIOTAP`.
+pub const LINKTYPE_RADIOTAP: u32 = 127;
+
+const PCAP_MAGIC: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NS: u32 = 0xa1b2_3c4d;
+const PCAPNG_SECTION: u32 = 0x0a0d_0d0a;
+const PCAPNG_BYTE_ORDER: u32 = 0x1a2b_3c4d;
+const PCAPNG_INTERFACE: u32 = 1;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+
+/// The file format.
+#[derive(Debug)]
+enum Format {
+    Pcap,
+    Pcapng,
+}
+
+/// An iterator over the Radiotap records in a pcap or pcapng file, created
+/// with [from_reader](fn.from_reader.html).
+///
+/// Yields the parsed capture and the 802.11 frame following it for each
+/// record. A record that fails to parse yields an error and the iterator
+/// continues with the next record, an error reading the file yields an error
+/// and ends the iteration.
+#[derive(Debug)]
+pub struct Records<R> {
+    reader: R,
+    big_endian: bool,
+    format: Format,
+    /// The link types of the interfaces in the current pcapng section.
+    interfaces: Vec<u32>,
+    done: bool,
+}
+
+/// Returns an iterator over the Radiotap records read from a pcap or pcapng
+/// file.
+///
+/// The byte order is taken from the magic number of the file. A pcap file
+/// must have the Radiotap link type. In a pcapng file the packets of
+/// interfaces with other link types are skipped.
+pub fn from_reader<R: Read>(mut reader: R) -> Result<Records<R>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+
+    let (format, big_endian) = match u32::from_le_bytes(magic) {
+        PCAP_MAGIC | PCAP_MAGIC_NS => (Format::Pcap, false),
+        magic if matches!(magic.swap_bytes(), PCAP_MAGIC | PCAP_MAGIC_NS) => (Format::Pcap, true),
+        PCAPNG_SECTION => (Format::Pcapng, false),
+        _ => return Err(Error::invalid_format()),
+    };
+
+    let mut records = Records {
+        reader,
+        big_endian,
+        format,
+        interfaces: Vec::new(),
+        done: false,
+    };
+
+    match records.format {
+        Format::Pcap => {
+            let mut header = [0; 20];
+            records.reader.read_exact(&mut header)?;
+            // The upper bits of the link type may hold FCS information
+            let link_type = records.u32(&header[16..20]) & 0xffff;
+            if link_type != LINKTYPE_RADIOTAP {
+                return Err(Error::InvalidFormat {
+                    offset: 20,
+                    kind: None,
+                    value: None,
+                });
+            }
+        }
+        Format::Pcapng => records.read_section()?,
+    }
+
+    Ok(records)
+}
+
+impl<R: Read> Records<R> {
+    /// Returns the `u32` in the byte order of the file.
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    /// Returns the `u16` in the byte order of the file.
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    /// Fills the buffer, returns false if the reader is already at the end.
+    fn read_or_eof(&mut self, buf: &mut [u8]) -> io::Result<bool> {
+        let mut read = 0;
+        while read == 0 {
+            match self.reader.read(buf) {
+                Ok(0) => return Ok(false),
+                Ok(n) => read = n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.reader.read_exact(&mut buf[read..])?;
+        Ok(true)
+    }
+
+    /// Reads the given number of bytes.
+    fn read_vec(&mut self, length: usize) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(&mut data)?;
+        if data.len() < length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(data)
+    }
+
+    /// Reads the rest of a pcapng section header block, after the block type.
+    fn read_section(&mut self) -> Result<()> {
+        let mut header = [0; 8];
+        self.reader.read_exact(&mut header)?;
+
+        self.big_endian = match u32::from_le_bytes([header[4], header[5], header[6], header[7]]) {
+            PCAPNG_BYTE_ORDER => false,
+            magic if magic.swap_bytes() == PCAPNG_BYTE_ORDER => true,
+            _ => return Err(Error::invalid_format()),
+        };
+
+        let length = self.u32(&header[..4]) as usize;
+        if length < 12 {
+            return Err(Error::invalid_format());
+        }
+        self.read_vec(length - 12)?;
+        // Interfaces are numbered from the start of each section
+        self.interfaces.clear();
+        Ok(())
+    }
+
+    /// Returns the data of the next pcap record.
+    fn next_pcap(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut header = [0; 16];
+        if !self.read_or_eof(&mut header)? {
+            return Ok(None);
+        }
+        let length = self.u32(&header[8..12]) as usize;
+        Ok(Some(self.read_vec(length)?))
+    }
+
+    /// Returns the data of the next pcapng packet from a Radiotap interface.
+    fn next_pcapng(&mut self) -> Result<Option<Vec<u8>>> {
+        loop {
+            let mut header = [0; 4];
+            if !self.read_or_eof(&mut header)? {
+                return Ok(None);
+            }
+
+            let block_type = self.u32(&header);
+            if block_type == PCAPNG_SECTION {
+                self.read_section()?;
+                continue;
+            }
+
+            self.reader.read_exact(&mut header)?;
+            let length = self.u32(&header) as usize;
+            if length < 12 {
+                return Err(Error::invalid_format());
+            }
+            // The body is followed by the repeated block length
+            let body = self.read_vec(length - 8)?;
+            let body = &body[..body.len() - 4];
+
+            let (interface, data) = match block_type {
+                PCAPNG_INTERFACE if body.len() >= 2 => {
+                    let link_type = self.u16(&body[..2]);
+                    self.interfaces.push(u32::from(link_type));
+                    continue;
+                }
+                PCAPNG_ENHANCED_PACKET if body.len() >= 20 => {
+                    let interface = self.u32(&body[..4]) as usize;
+                    let captured = self.u32(&body[12..16]) as usize;
+                    (interface, body[20..].get(..captured))
+                }
+                PCAPNG_SIMPLE_PACKET if body.len() >= 4 => {
+                    let original = self.u32(&body[..4]) as usize;
+                    (0, Some(&body[4..4 + original.min(body.len() - 4)]))
+                }
+                _ => continue,
+            };
+
+            let data = data.ok_or_else(Error::invalid_format)?;
+            if self.interfaces.get(interface) == Some(&LINKTYPE_RADIOTAP) {
+                return Ok(Some(data.to_vec()));
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Records<R> {
+    type Item = Result<(Radiotap, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let data = match self.format {
+            Format::Pcap => self.next_pcap(),
+            Format::Pcapng => self.next_pcapng(),
+        };
+
+        match data {
+            Ok(Some(data)) => {
+                Some(Radiotap::parse(&data).map(|(radiotap, payload)| (radiotap, payload.to_vec())))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Rate;
+
+    /// A little-endian pcap file with two Radiotap records.
+    const PCAP: [u8; 78] = [
+        0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 127, 0, 0, 0,
+        // Record with a rate of 1 Mbps and an ACK
+        0, 0, 0, 0, 0, 0, 0, 0, 13, 0, 0, 0, 13, 0, 0, 0, 0, 0, 9, 0, 4, 0, 0, 0, 2, 0xd4, 0, 0,
+        0, // Record with a rate of 6 Mbps and no payload
+        0, 0, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 9, 0, 0, 0, 0, 0, 9, 0, 4, 0, 0, 0, 12,
+    ];
+
+    fn rates<R: Read>(records: Records<R>) -> Vec<(f32, Vec<u8>)> {
+        records
+            .map(|record| record.unwrap())
+            .map(|(radiotap, payload)| (radiotap.rate.unwrap().value, payload))
+            .collect()
+    }
+
+    #[test]
+    fn pcap() {
+        let records = from_reader(&PCAP[..]).unwrap();
+        assert_eq!(rates(records), [(1.0, vec![0xd4, 0, 0, 0]), (6.0, vec![])]);
+    }
+
+    #[test]
+    fn pcap_big_endian() {
+        let mut pcap = PCAP;
+        // Swap the byte order of the header fields and record lengths
+        for range in [
+            0..4,
+            4..6,
+            6..8,
+            16..20,
+            20..24,
+            32..36,
+            36..40,
+            61..65,
+            65..69,
+        ] {
+            pcap[range].reverse();
+        }
+        let records = from_reader(&pcap[..]).unwrap();
+        assert_eq!(rates(records), [(1.0, vec![0xd4, 0, 0, 0]), (6.0, vec![])]);
+    }
+
+    #[test]
+    fn pcap_link_type() {
+        let mut pcap = PCAP;
+        pcap[20] = 105;
+        assert!(matches!(
+            from_reader(&pcap[..]),
+            Err(Error::InvalidFormat { offset: 20, .. })
+        ));
+        assert!(from_reader(&[0; 24][..]).is_err());
+    }
+
+    #[test]
+    fn pcap_truncated() {
+        let mut records = from_reader(&PCAP[..60]).unwrap();
+        assert!(records.next().unwrap().is_ok());
+        assert!(matches!(records.next(), Some(Err(Error::ParseError(_)))));
+        assert!(records.next().is_none());
+    }
+
+    /// Returns a little-endian pcapng block.
+    fn block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let length = (12 + body.len()) as u32;
+        let mut block = block_type.to_le_bytes().to_vec();
+        block.extend_from_slice(&length.to_le_bytes());
+        block.extend_from_slice(body);
+        block.extend_from_slice(&length.to_le_bytes());
+        block
+    }
+
+    /// Returns an enhanced packet block body for the interface.
+    fn packet(interface: u32, data: &[u8]) -> Vec<u8> {
+        let mut body = interface.to_le_bytes().to_vec();
+        body.extend_from_slice(&[0; 8]);
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(data);
+        body.resize((body.len() + 3) & !3, 0);
+        body
+    }
+
+    #[test]
+    fn pcapng() {
+        let rate = |value| crate::RadiotapBuilder::new().rate(Rate { value }).build();
+
+        let mut pcapng = block(
+            PCAPNG_SECTION,
+            &[
+                0x4d, 0x3c, 0x2b, 0x1a, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+        );
+        // A Radiotap interface and an 802.11 interface
+        pcapng.extend(block(PCAPNG_INTERFACE, &[127, 0, 0, 0, 0, 0, 0, 0]));
+        pcapng.extend(block(PCAPNG_INTERFACE, &[105, 0, 0, 0, 0, 0, 0, 0]));
+        pcapng.extend(block(PCAPNG_ENHANCED_PACKET, &packet(0, &rate(1.0))));
+        pcapng.extend(block(PCAPNG_ENHANCED_PACKET, &packet(1, &[0xd4, 0, 0, 0])));
+        pcapng.extend(block(PCAPNG_ENHANCED_PACKET, &packet(0, &rate(6.0))));
+        // An unknown block is skipped
+        pcapng.extend(block(5, &[0; 4]));
+
+        let records = from_reader(&pcapng[..]).unwrap();
+        assert_eq!(rates(records), [(1.0, vec![]), (6.0, vec![])]);
+    }
+}
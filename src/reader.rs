@@ -0,0 +1,96 @@
+//! A reader for the little-endian values in a Radiotap capture.
+//!
+//! All multi-byte values in a capture are little-endian whatever the host, so
+//! every field is read through this reader and never with native-endian
+//! reads. Fields are written with `to_le_bytes` for the same reason.
+
+use crate::{Error, Result};
+
+/// Reads little-endian values from a byte slice, keeping track of the
+/// position.
+#[derive(Clone, Debug)]
+pub(crate) struct ByteReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    /// Returns a reader positioned at the start of the data.
+    pub fn new(data: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { data, position: 0 }
+    }
+
+    /// Returns the underlying data.
+    pub fn get_ref(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Sets the current position.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Aligns the position to `align` size, usually 1, 2, 4, or 8.
+    pub fn align(&mut self, align: u64) {
+        let align = align.max(1) as usize;
+        self.position = self.position.saturating_add(align - 1) & !(align - 1);
+    }
+
+    /// Returns the next `size` bytes and advances past them.
+    fn take(&mut self, size: usize) -> Result<&'a [u8]> {
+        let end = match self.position.checked_add(size) {
+            Some(end) if end <= self.data.len() => end,
+            _ => {
+                return Err(Error::IncompleteError {
+                    offset: self.position,
+                    kind: None,
+                })
+            }
+        };
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /// Fills the buffer with the next bytes.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        buf.copy_from_slice(self.take(buf.len())?);
+        Ok(())
+    }
+
+    /// Reads a `u8`.
+    pub fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Reads an `i8`.
+    pub fn read_i8(&mut self) -> Result<i8> {
+        Ok(self.read_u8()? as i8)
+    }
+
+    /// Reads a `u16`.
+    pub fn read_u16(&mut self) -> Result<u16> {
+        let mut bytes = [0; 2];
+        self.read_exact(&mut bytes)?;
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    /// Reads a `u32`.
+    pub fn read_u32(&mut self) -> Result<u32> {
+        let mut bytes = [0; 4];
+        self.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    /// Reads a `u64`.
+    pub fn read_u64(&mut self) -> Result<u64> {
+        let mut bytes = [0; 8];
+        self.read_exact(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
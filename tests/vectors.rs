@@ -0,0 +1,310 @@
+//! Decodes captures covering the parsed fields and checks every sub-field
+//! against the expected [Radiotap](../radiotap/struct.Radiotap.html).
+
+use radiotap::{
+    field::{ext::*, *},
+    Radiotap,
+};
+
+/// Returns the header of a capture with a single present word.
+fn header(length: usize, present: &[Kind]) -> Header {
+    Header {
+        version: 0,
+        length,
+        size: 8,
+        present: present.into(),
+        tlv: false,
+        skipped: Vec::new(),
+        resets: Vec::new(),
+    }
+}
+
+/// A received VHT frame.
+///
+/// - TSFT of 2593857465 us
+/// - Flags: WEP and FCS
+/// - Channel 5500 MHz, OFDM 5 GHz
+/// - Antenna signal -43 dBm, antenna noise -90 dBm, antenna 1
+/// - XChannel 5500 MHz, channel 100, HT20, max power 34 dBm
+/// - A-MPDU reference 505, no flags known
+/// - VHT 80 MHz, long GI, LDPC extra symbol, group 63, partial AID 0, a
+///   single user with MCS 7, 3 spatial streams, LDPC, 877.5 Mbps
+const VHT_CAPTURE: [u8; 56] = [
+    0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213, 166, 1,
+    0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80, 4, 115, 0, 0, 0,
+    1, 63, 0, 0,
+];
+
+/// A received HT frame.
+///
+/// - Flags: none set
+/// - Channel 2437 MHz, dynamic CCK-OFDM 2.4 GHz
+/// - Antenna signal -56 dBm
+/// - MCS 7, 20 MHz, short GI, HT mixed, LDPC, 3 STBC streams, 72.2 Mbps
+const MCS_CAPTURE: [u8; 18] = [
+    0, 0, 18, 0, 42, 0, 8, 0, 0, 0, 0x85, 0x09, 0x80, 0x04, 0xc8, 0x3f, 0x74, 7,
+];
+
+/// A timestamp of 10000 us at the start of the PLCP, with an accuracy of
+/// 10 us.
+const TIMESTAMP_CAPTURE: [u8; 20] = [
+    0, 0, 20, 0, 0, 0, 64, 0, 0x10, 0x27, 0, 0, 0, 0, 0, 0, 0x0a, 0, 0x11, 0x02,
+];
+
+/// A received legacy frame.
+///
+/// - TSFT of 67305985 us
+/// - Flags: short preamble
+/// - Rate 6 Mbps
+/// - Channel 2437 MHz, OFDM 2.4 GHz
+/// - Antenna signal -60 dBm, antenna noise -95 dBm, antenna 0
+/// - RX flags: bad PLCP
+const LEGACY_CAPTURE: [u8; 28] = [
+    0, 0, 28, 0, 0x6f, 0x48, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0, 0x02, 0x0c, 0x85, 0x09, 0xc0, 0, 0xc4,
+    0xa1, 0, 0, 2, 0,
+];
+
+#[test]
+fn vht() {
+    let expected = Radiotap {
+        header: header(
+            56,
+            &[
+                Kind::TSFT,
+                Kind::Flags,
+                Kind::Channel,
+                Kind::AntennaSignal,
+                Kind::AntennaNoise,
+                Kind::Antenna,
+                Kind::XChannel,
+                Kind::AMPDUStatus,
+                Kind::VHT,
+            ],
+        ),
+        tsft: Some(TSFT { value: 2593857465 }),
+        flags: Some(Flags {
+            cfp: false,
+            preamble: false,
+            wep: true,
+            fragmentation: false,
+            fcs: true,
+            data_pad: false,
+            bad_fcs: false,
+            sgi: false,
+        }),
+        channel: Some(Channel {
+            freq: 5500,
+            flags: ChannelFlags {
+                turbo: false,
+                cck: false,
+                ofdm: true,
+                ghz2: false,
+                ghz5: true,
+                passive: false,
+                dynamic: false,
+                gfsk: false,
+            },
+        }),
+        antenna_signal: Some(AntennaSignal { value: -43 }),
+        antenna_noise: Some(AntennaNoise { value: -90 }),
+        antenna: Some(Antenna { value: 1 }),
+        xchannel: Some(XChannel {
+            flags: XChannelFlags {
+                turbo: false,
+                cck: false,
+                ofdm: true,
+                ghz2: false,
+                ghz5: true,
+                passive: false,
+                dynamic: false,
+                gfsk: false,
+                gsm: false,
+                sturbo: false,
+                half: false,
+                quarter: false,
+                ht20: true,
+                ht40u: false,
+                ht40d: false,
+            },
+            freq: 5500,
+            channel: 100,
+            max_power: 34,
+        }),
+        ampdu_status: Some(AMPDUStatus {
+            reference: 505,
+            zero_length: None,
+            last: None,
+            delimiter_crc: None,
+            delimiter_crc_error: Some(false),
+            eof: None,
+        }),
+        vht: Some(VHT {
+            stbc: Some(false),
+            txop_ps: Some(false),
+            gi: Some(GuardInterval::Long),
+            sgi_nsym_da: Some(false),
+            ldpc_extra: Some(true),
+            beamformed: Some(false),
+            bw: Some(Bandwidth {
+                bandwidth: 80,
+                sideband: None,
+                sideband_index: None,
+            }),
+            group_id: Some(63),
+            partial_aid: Some(0),
+            users: [
+                Some(VHTUser {
+                    index: 7,
+                    fec: FEC::LDPC,
+                    nss: 3,
+                    nsts: 3,
+                    datarate: Some(877.5),
+                }),
+                None,
+                None,
+                None,
+            ],
+        }),
+        antenna_signals: vec![(1, -43)],
+        ..Radiotap::default()
+    };
+
+    assert_eq!(Radiotap::from_bytes(&VHT_CAPTURE).unwrap(), expected);
+}
+
+#[test]
+fn mcs() {
+    let expected = Radiotap {
+        header: header(
+            18,
+            &[Kind::Flags, Kind::Channel, Kind::AntennaSignal, Kind::MCS],
+        ),
+        flags: Some(Flags {
+            cfp: false,
+            preamble: false,
+            wep: false,
+            fragmentation: false,
+            fcs: false,
+            data_pad: false,
+            bad_fcs: false,
+            sgi: false,
+        }),
+        channel: Some(Channel {
+            freq: 2437,
+            flags: ChannelFlags {
+                turbo: false,
+                cck: false,
+                ofdm: false,
+                ghz2: true,
+                ghz5: false,
+                passive: false,
+                dynamic: true,
+                gfsk: false,
+            },
+        }),
+        antenna_signal: Some(AntennaSignal { value: -56 }),
+        mcs: Some(MCS {
+            bw: Some(Bandwidth {
+                bandwidth: 20,
+                sideband: None,
+                sideband_index: None,
+            }),
+            index: Some(7),
+            gi: Some(GuardInterval::Short),
+            format: Some(HTFormat::Mixed),
+            fec: Some(FEC::LDPC),
+            stbc: Some(3),
+            ness: None,
+            datarate: Some(72.2),
+        }),
+        ..Radiotap::default()
+    };
+
+    assert_eq!(Radiotap::from_bytes(&MCS_CAPTURE).unwrap(), expected);
+}
+
+#[test]
+fn timestamp() {
+    let expected = Radiotap {
+        header: header(20, &[Kind::Timestamp]),
+        timestamp: Some(Timestamp {
+            timestamp: 10000,
+            unit: TimeUnit::Microseconds,
+            position: SamplingPosition::StartPLCP,
+            accuracy: Some(10),
+            counter_32bit: false,
+        }),
+        ..Radiotap::default()
+    };
+
+    assert_eq!(Radiotap::from_bytes(&TIMESTAMP_CAPTURE).unwrap(), expected);
+}
+
+#[test]
+fn legacy() {
+    let expected = Radiotap {
+        header: header(
+            28,
+            &[
+                Kind::TSFT,
+                Kind::Flags,
+                Kind::Rate,
+                Kind::Channel,
+                Kind::AntennaSignal,
+                Kind::AntennaNoise,
+                Kind::Antenna,
+                Kind::RxFlags,
+            ],
+        ),
+        tsft: Some(TSFT { value: 67305985 }),
+        flags: Some(Flags {
+            cfp: false,
+            preamble: true,
+            wep: false,
+            fragmentation: false,
+            fcs: false,
+            data_pad: false,
+            bad_fcs: false,
+            sgi: false,
+        }),
+        rate: Some(Rate { value: 6.0 }),
+        channel: Some(Channel {
+            freq: 2437,
+            flags: ChannelFlags {
+                turbo: false,
+                cck: false,
+                ofdm: true,
+                ghz2: true,
+                ghz5: false,
+                passive: false,
+                dynamic: false,
+                gfsk: false,
+            },
+        }),
+        antenna_signal: Some(AntennaSignal { value: -60 }),
+        antenna_noise: Some(AntennaNoise { value: -95 }),
+        antenna: Some(Antenna { value: 0 }),
+        rx_flags: Some(RxFlags { bad_plcp: true }),
+        antenna_signals: vec![(0, -60)],
+        ..Radiotap::default()
+    };
+
+    assert_eq!(Radiotap::from_bytes(&LEGACY_CAPTURE).unwrap(), expected);
+}
+
+#[test]
+fn roundtrip() {
+    // Encoding the parsed vectors and parsing them again gives the same fields
+    for capture in [
+        &VHT_CAPTURE[..],
+        &MCS_CAPTURE[..],
+        &TIMESTAMP_CAPTURE[..],
+        &LEGACY_CAPTURE[..],
+    ] {
+        let radiotap = Radiotap::from_bytes(capture).unwrap();
+        assert_eq!(
+            Radiotap::from_bytes(&radiotap.to_bytes()).unwrap(),
+            radiotap
+        );
+    }
+}